@@ -1,8 +1,9 @@
+use std::path::PathBuf;
+
 /// Common arguments shared by all commands
 #[derive(Debug, Clone)]
 pub struct CommonArgs {
     pub dry_run: bool,
-    #[allow(dead_code)] // Will be used in future phases
     pub verbose: bool,
     pub message: Option<String>,
 }
@@ -11,6 +12,17 @@ pub struct CommonArgs {
 #[derive(Debug, Clone)]
 pub struct CommitArgs {
     pub common: CommonArgs,
+    pub include: Vec<PathBuf>,
+    pub stat: bool,
+    pub oneline: bool,
+    pub no_verify: bool,
+    pub no_ticket: bool,
+    pub signoff: bool,
+    pub only: Vec<String>,
+    pub template_from_commit: Option<String>,
+    pub json: bool,
+    pub message_only: bool,
+    pub allow_empty: bool,
     pub no_confirm: bool,
 }
 
@@ -18,6 +30,13 @@ pub struct CommitArgs {
 #[derive(Debug, Clone)]
 pub struct PrArgs {
     pub common: CommonArgs,
+    pub include: Vec<PathBuf>,
+    pub patch: Option<PathBuf>,
+    pub stat: bool,
+    pub no_ticket: bool,
+    pub split: bool,
+    pub output: Option<PathBuf>,
+    pub reviewers: bool,
     pub no_confirm: bool,
 }
 
@@ -26,6 +45,8 @@ pub struct PrArgs {
 pub struct MergeArgs {
     pub common: CommonArgs,
     pub branch: String,
+    pub preview_conflicts: bool,
+    pub force: bool,
     pub no_confirm: bool,
 }
 
@@ -34,6 +55,12 @@ pub struct MergeArgs {
 pub struct ConfigArgs {
     pub show: bool,
     pub init: bool,
+    pub migrate: bool,
+    pub effective: bool,
+    /// Dotted key and the value to set it to
+    pub set: Option<(String, String)>,
+    pub unset: Option<String>,
+    pub global: bool,
 }
 
 /// Arguments specific to init command
@@ -42,6 +69,11 @@ pub struct InitArgs {
     pub common: CommonArgs,
     pub language: Option<String>,
     pub name: Option<String>,
+    pub non_interactive: bool,
+    pub package_manager: Option<String>,
+    pub ci: bool,
+    pub license: Option<String>,
+    pub no_readme: bool,
     pub no_confirm: bool,
 }
 
@@ -55,3 +87,53 @@ pub struct IgnoreArgs {
     #[allow(dead_code)] // Will be used in future phases
     pub verbose: bool,
 }
+
+/// Arguments specific to amend-files command
+#[derive(Debug, Clone)]
+pub struct AmendFilesArgs {
+    pub common: CommonArgs,
+    pub no_confirm: bool,
+}
+
+/// Arguments specific to reword command
+#[derive(Debug, Clone)]
+pub struct RewordArgs {
+    pub common: CommonArgs,
+    pub rev: String,
+    pub force: bool,
+    pub no_confirm: bool,
+}
+
+/// Arguments specific to resolve command
+#[derive(Debug, Clone)]
+pub struct ResolveArgs {
+    pub common: CommonArgs,
+    pub no_confirm: bool,
+}
+
+/// Arguments specific to undo command
+#[derive(Debug, Clone)]
+pub struct UndoArgs {
+    pub no_confirm: bool,
+    pub dry_run: bool,
+}
+
+/// Arguments specific to prepush command
+#[derive(Debug, Clone)]
+pub struct PrepushArgs {
+    pub no_block: bool,
+}
+
+/// Arguments specific to ship command
+#[derive(Debug, Clone)]
+pub struct ShipArgs {
+    pub common: CommonArgs,
+    pub no_confirm: bool,
+}
+
+/// Arguments specific to completions command
+#[derive(Debug, Clone)]
+pub struct CompletionsArgs {
+    pub shell: Option<clap_complete::Shell>,
+    pub install: bool,
+}
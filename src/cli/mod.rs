@@ -1,13 +1,19 @@
 pub mod args;
 
 use crate::commands::{
-    Command, CommitCommand, ConfigCommand, IgnoreCommand, InitCommand, MergeCommand, PrCommand,
+    AmendFilesCommand, Command, CommitCommand, CompletionsCommand, ConfigCommand, IgnoreCommand,
+    InitCommand, MergeCommand, PrCommand, PrepushCommand, ResolveCommand, RewordCommand,
+    ShipCommand, StatsCommand, UndoCommand,
 };
 use crate::config::Config;
 use crate::cursor_agent::CursorAgent;
-use crate::{Commands, IgnoreAction};
+use crate::history::{self, History};
+use crate::{git, Commands, IgnoreAction};
 use anyhow::Result;
-use args::{CommitArgs, CommonArgs, ConfigArgs, IgnoreArgs, InitArgs, MergeArgs, PrArgs};
+use args::{
+    AmendFilesArgs, CommitArgs, CommonArgs, CompletionsArgs, ConfigArgs, IgnoreArgs, InitArgs,
+    MergeArgs, PrArgs, PrepushArgs, ResolveArgs, RewordArgs, ShipArgs, UndoArgs,
+};
 
 /// Command dispatcher that routes CLI commands to their implementations
 pub struct CommandDispatcher {
@@ -16,17 +22,44 @@ pub struct CommandDispatcher {
 }
 
 impl CommandDispatcher {
-    pub fn new(config: Config) -> Self {
-        Self {
-            config,
-            agent: CursorAgent::new(),
-        }
+    pub fn new(config: Config, agent: CursorAgent) -> Self {
+        Self { config, agent }
     }
 
     pub async fn dispatch(&self, command: Commands) -> Result<()> {
+        // Remember where HEAD was, and the files the command edits directly,
+        // so its commits and file changes can be undone
+        let recorded_as = undoable_command_name(&command);
+        let head_before = git::head_commit();
+        let files_before = match recorded_as {
+            Some(_) => history::snapshot(edited_files(&command)),
+            None => Vec::new(),
+        };
+
+        self.run(command).await?;
+
+        if let Some(name) = recorded_as {
+            History::record_if_changed(name, head_before, files_before)?;
+        }
+
+        Ok(())
+    }
+
+    async fn run(&self, command: Commands) -> Result<()> {
         match command {
             Commands::Commit {
                 message,
+                include,
+                stat,
+                oneline,
+                no_verify,
+                no_ticket,
+                signoff,
+                only,
+                template_from_commit,
+                json,
+                message_only,
+                allow_empty,
                 no_confirm,
                 dry_run,
                 verbose,
@@ -37,6 +70,17 @@ impl CommandDispatcher {
                         verbose,
                         message,
                     },
+                    include,
+                    stat,
+                    oneline,
+                    no_verify,
+                    no_ticket,
+                    signoff,
+                    only,
+                    template_from_commit,
+                    json,
+                    message_only,
+                    allow_empty,
                     no_confirm,
                 };
                 let cmd = CommitCommand::new(self.config.commands.commit.clone());
@@ -45,6 +89,13 @@ impl CommandDispatcher {
             }
             Commands::Pr {
                 message,
+                include,
+                patch,
+                stat,
+                no_ticket,
+                split,
+                output,
+                reviewers,
                 no_confirm,
                 dry_run,
                 verbose,
@@ -55,6 +106,13 @@ impl CommandDispatcher {
                         verbose,
                         message,
                     },
+                    include,
+                    patch,
+                    stat,
+                    no_ticket,
+                    split,
+                    output,
+                    reviewers,
                     no_confirm,
                 };
                 let cmd = PrCommand::new(self.config.commands.pr.clone());
@@ -64,6 +122,8 @@ impl CommandDispatcher {
             Commands::Merge {
                 branch,
                 message,
+                preview_conflicts,
+                force,
                 no_confirm,
                 dry_run,
                 verbose,
@@ -75,20 +135,44 @@ impl CommandDispatcher {
                         message,
                     },
                     branch,
+                    preview_conflicts,
+                    force,
                     no_confirm,
                 };
                 let cmd = MergeCommand::new(self.config.commands.merge.clone());
                 let resolved_args = cmd.resolve_args(args);
                 cmd.execute(resolved_args, &self.agent).await
             }
-            Commands::Config { show, init } => {
-                let args = ConfigArgs { show, init };
-                let cmd = ConfigCommand::new();
+            Commands::Config {
+                show,
+                init,
+                migrate,
+                effective,
+                set,
+                unset,
+                global,
+            } => {
+                let args = ConfigArgs {
+                    show,
+                    init,
+                    migrate,
+                    effective,
+                    // clap guarantees exactly two values for --set
+                    set: set.map(|pair| (pair[0].clone(), pair[1].clone())),
+                    unset,
+                    global,
+                };
+                let cmd = ConfigCommand::new(self.config.clone());
                 cmd.execute(args, &self.agent).await
             }
             Commands::Init {
                 language,
                 name,
+                non_interactive,
+                package_manager,
+                ci,
+                license,
+                no_readme,
                 message,
                 no_confirm,
                 dry_run,
@@ -102,6 +186,11 @@ impl CommandDispatcher {
                     },
                     language,
                     name,
+                    non_interactive,
+                    package_manager,
+                    ci,
+                    license,
+                    no_readme,
                     no_confirm,
                 };
                 let cmd = InitCommand::new(self.config.commands.init.clone());
@@ -135,6 +224,142 @@ impl CommandDispatcher {
                 let resolved_args = cmd.resolve_args(args);
                 cmd.execute(resolved_args, &self.agent).await
             }
+            Commands::AmendFiles {
+                message,
+                no_confirm,
+                dry_run,
+                verbose,
+            } => {
+                let args = AmendFilesArgs {
+                    common: CommonArgs {
+                        dry_run,
+                        verbose,
+                        message,
+                    },
+                    no_confirm,
+                };
+                let cmd = AmendFilesCommand::new(self.config.commands.amend_files.clone());
+                let resolved_args = cmd.resolve_args(args);
+                cmd.execute(resolved_args, &self.agent).await
+            }
+            Commands::Resolve {
+                message,
+                no_confirm,
+                dry_run,
+                verbose,
+            } => {
+                let args = ResolveArgs {
+                    common: CommonArgs {
+                        dry_run,
+                        verbose,
+                        message,
+                    },
+                    no_confirm,
+                };
+                let cmd = ResolveCommand::new(self.config.commands.resolve.clone());
+                let resolved_args = cmd.resolve_args(args);
+                cmd.execute(resolved_args, &self.agent).await
+            }
+            Commands::Reword {
+                rev,
+                message,
+                force,
+                no_confirm,
+                dry_run,
+                verbose,
+            } => {
+                let args = RewordArgs {
+                    common: CommonArgs {
+                        dry_run,
+                        verbose,
+                        message,
+                    },
+                    rev,
+                    force,
+                    no_confirm,
+                };
+                let cmd = RewordCommand::new(
+                    self.config.commands.reword.clone(),
+                    self.config.commands.commit.clone(),
+                );
+                let resolved_args = cmd.resolve_args(args);
+                cmd.execute(resolved_args, &self.agent).await
+            }
+            Commands::Undo {
+                no_confirm,
+                dry_run,
+            } => {
+                let args = UndoArgs {
+                    no_confirm,
+                    dry_run,
+                };
+                let cmd = UndoCommand::new();
+                cmd.execute(args, &self.agent).await
+            }
+            Commands::Prepush { no_block } => {
+                let args = PrepushArgs { no_block };
+                let cmd = PrepushCommand::new(self.config.commands.prepush.clone());
+                cmd.execute(args, &self.agent).await
+            }
+            Commands::Stats => {
+                let cmd = StatsCommand::new();
+                cmd.execute((), &self.agent).await
+            }
+            Commands::Ship {
+                message,
+                no_confirm,
+                dry_run,
+                verbose,
+            } => {
+                let args = ShipArgs {
+                    common: CommonArgs {
+                        dry_run,
+                        verbose,
+                        message,
+                    },
+                    no_confirm,
+                };
+                let cmd = ShipCommand::new(
+                    self.config.commands.ship.clone(),
+                    self.config.commands.commit.clone(),
+                    self.config.commands.pr.clone(),
+                );
+                let resolved_args = cmd.resolve_args(args);
+                cmd.execute(resolved_args, &self.agent).await
+            }
+            Commands::Completions { shell, install } => {
+                let args = CompletionsArgs { shell, install };
+                let cmd = CompletionsCommand::new();
+                cmd.execute(args, &self.agent).await
+            }
         }
     }
 }
+
+/// Files, relative to the repository root, that a command edits itself rather
+/// than only through commits
+fn edited_files(command: &Commands) -> &'static [&'static str] {
+    match command {
+        Commands::Ignore { .. } => &[".gitignore"],
+        _ => &[],
+    }
+}
+
+/// Name recorded in the undo history for commands that may create commits or edit files
+fn undoable_command_name(command: &Commands) -> Option<&'static str> {
+    match command {
+        Commands::Commit { dry_run: false, .. } => Some("commit"),
+        Commands::Pr { dry_run: false, .. } => Some("pr"),
+        Commands::Merge { dry_run: false, .. } => Some("merge"),
+        Commands::Reword { dry_run: false, .. } => Some("reword"),
+        Commands::Ship { dry_run: false, .. } => Some("ship"),
+        Commands::Init { dry_run: false, .. } => Some("init"),
+        Commands::AmendFiles { dry_run: false, .. } => Some("amend-files"),
+        Commands::Ignore { action } => match action {
+            IgnoreAction::Add { dry_run: false, .. } => Some("ignore add"),
+            IgnoreAction::Remove { dry_run: false, .. } => Some("ignore remove"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,108 @@
+use crate::cli::args::AmendFilesArgs;
+use crate::commands::Command;
+use crate::config::AmendFilesConfig;
+use crate::context;
+use crate::cursor_agent::CursorAgent;
+use crate::terminal::{icon, Icon};
+use anyhow::Result;
+
+/// AI-assisted maintenance prompt for managed tooling files
+const AMEND_FILES_PROMPT: &str = r#"You are operating inside a command line interface as an AI assistant integrated with Git via `cursor-agent`.
+
+Your task is to bring the project's managed tooling files back in sync with the current state of the repository, then commit the result in a single step.
+
+---
+
+## Steps
+
+1. **Detect the Project's Languages and Tools**
+   - Inspect the repository (manifests such as `Cargo.toml`, `package.json`, `pyproject.toml`, `go.mod`, lock files, and file extensions) to determine which languages, package managers, and editors/tools are in use.
+
+2. **Regenerate `.gitignore` Sections**
+   - `.gitignore` is organized into marker-delimited sections, one per language or tool:
+     ```
+     # === <Language> ===
+     <patterns>
+     # === End <Language> ===
+     ```
+   - Add a section for every detected language/tool that is missing one.
+   - Refresh existing sections with current best-practice patterns.
+   - Remove sections for languages/tools that are no longer present in the project.
+   - Never modify entries outside of marker-delimited sections; those were added by hand.
+
+3. **Regenerate `.editorconfig`**
+   - Create or update `.editorconfig` with `root = true` and sections matching the detected languages' conventional indentation and line-ending settings.
+   - Preserve any existing custom settings that don't conflict with the detected languages.
+
+4. **Review and Commit**
+   - Display a summary of the changes made to each file.
+   - If nothing changed, report that the files are already in sync and stop without committing.
+   - Otherwise stage only the managed files and commit them:
+     ```
+     git add .gitignore .editorconfig
+     git commit -m "chore: sync managed tooling files"
+     ```
+   - Mention the affected languages/tools in the commit body.
+
+---
+
+## Guidelines
+
+- Only touch `.gitignore` and `.editorconfig`; leave all other files alone.
+- Do not include unrelated staged changes in the commit.
+- Keep the files readable, with comments where they help."#;
+
+/// Command for regenerating managed tooling files and committing them
+pub struct AmendFilesCommand {
+    config: AmendFilesConfig,
+}
+
+impl AmendFilesCommand {
+    pub fn new(config: AmendFilesConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Command for AmendFilesCommand {
+    type Args = AmendFilesArgs;
+    type Config = AmendFilesConfig;
+
+    fn prompt_template(&self) -> &str {
+        // Use custom prompt from config if available, otherwise use built-in
+        self.config.prompt.as_deref().unwrap_or(AMEND_FILES_PROMPT)
+    }
+
+    fn resolve_args(&self, mut args: Self::Args) -> Self::Args {
+        // Apply config overrides
+        if let Some(no_confirm) = self.config.no_confirm {
+            args.no_confirm = no_confirm;
+        }
+        args
+    }
+
+    async fn execute(&self, args: AmendFilesArgs, agent: &CursorAgent) -> Result<()> {
+        let mut prompt = self.prompt_template().to_string();
+
+        // Add user message if provided
+        if let Some(always) = context::always_included_section() {
+            prompt = format!("{}\n\n{}", prompt, always);
+        }
+
+        if let Some(ref message) = args.common.message {
+            prompt = format!("{}\n\nUser Context: {}", prompt, message);
+        }
+
+        // Handle dry run
+        if args.common.dry_run {
+            println!(
+                "{}Dry run mode - would execute with prompt:\n---\n{}\n---",
+                icon(Icon::Search),
+                prompt
+            );
+            return Ok(());
+        }
+
+        // Execute with cursor-agent
+        agent.execute(&prompt, args.no_confirm).await
+    }
+}
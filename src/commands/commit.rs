@@ -1,8 +1,16 @@
 use crate::cli::args::CommitArgs;
 use crate::commands::Command;
-use crate::config::CommitConfig;
+use crate::config::{CoAuthorSource, CommitConfig, CommitGrouping};
+use crate::context;
 use crate::cursor_agent::CursorAgent;
-use anyhow::Result;
+use crate::git;
+use crate::language;
+use crate::pattern;
+use crate::spellcheck;
+use crate::terminal::{self, icon, Icon};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// Commit prompt template
 pub const COMMIT_PROMPT: &str =
@@ -19,7 +27,7 @@ Your task:
 2. **Generate commit messages following the Conventional Commits standard.**
    - Use the format: `<type>(<optional scope>): <short description>`
    - Keep each message concise and clear.
-   - Limit the body to **at most two bullet points**, summarizing what and why the change was made.
+   - Keep the body short, following the message body guidance below.
    - Subject line under **72 characters**, written in **present tense**.
    - Focus on **what changed** and **why**, not how.
 
@@ -41,6 +49,63 @@ fix(ui): correct navbar alignment on mobile
 - adjust CSS grid for better responsiveness
 ";
 
+/// Conventional commit types allowed when none are configured
+pub const DEFAULT_COMMIT_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
+/// Maximum bullet points in a commit body when none is configured
+pub const DEFAULT_BODY_BULLETS: usize = 2;
+
+/// Guidance for small changes that only warrant a subject line
+const ONELINE_GUIDANCE: &str =
+    "Message length: this is a small change. Write each commit message as a single \
+     conventional-commit subject line with NO body and no bullet points (trailers, if required \
+     below, still go after a blank line).";
+
+/// Trailer marking commits generated by git-ai, counted by `git ai stats`
+pub const GENERATED_BY_TRAILER: &str = "Generated-by: git-ai";
+
+/// Extra guidance when committing to a repository with no commits yet
+const INITIAL_COMMIT_GUIDANCE: &str =
+    "Repository state: this repository has no commits yet, so this will be the initial commit. \
+     There is no history to compare against or match: describe what the project contains as a whole \
+     (e.g. `chore: initialize project structure` or `feat: add initial <project> implementation`) \
+     rather than describing changes relative to earlier code.";
+
+/// Output format instructions for `--dry-run --json` commit plans
+const PLAN_OUTPUT_FORMAT: &str =
+    "Output format: do NOT run `git commit`, `git add`, or any command that changes the repository. \
+     Instead print ONLY a JSON array describing the commits you would create, in order, with nothing \
+     before or after it. Each element must be an object with a `message` string (the full commit \
+     message, including any body and required trailers) and a `files` array of the paths it commits, \
+     e.g. [{\"message\": \"feat(api): add login\", \"files\": [\"src/api.rs\"]}].";
+
+/// Replaces the change analysis for `--allow-empty`
+const EMPTY_COMMIT_GUIDANCE: &str =
+    "Empty commit: the user wants an intentional commit with no changes, for example to trigger CI \
+     or mark a milestone. Ignore the steps above about analyzing and grouping changes: do not stage, \
+     modify, or commit any files. Create exactly one commit with `git commit --allow-empty`, with a \
+     message stating its purpose, taken from the user context if given (e.g. `chore: trigger CI`).";
+
+/// Size in MB above which a committed file needs confirmation, when not configured
+const DEFAULT_LARGE_FILE_MB: u64 = 5;
+
+/// Appended for `--message-only` and `auto_commit: false`, which draft the
+/// message instead of committing
+const MESSAGE_OUTPUT_FORMAT: &str =
+    "Output format: do NOT run `git commit`, `git add`, or any command that changes the repository. \
+     Instead print ONLY the full commit message for these changes, including any body and required \
+     trailers, with no code fences, commentary, or other text before or after it.";
+
+/// A commit the AI proposes to create, as reported by `--dry-run --json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposedCommit {
+    pub message: String,
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
 /// Commit command implementation
 pub struct CommitCommand {
     config: CommitConfig,
@@ -50,6 +115,385 @@ impl CommitCommand {
     pub fn new(config: CommitConfig) -> Self {
         Self { config }
     }
+
+    /// Collect pairing co-authors from the configured source
+    fn co_authors(&self) -> Vec<String> {
+        let source = self.config.co_authors.unwrap_or_default();
+
+        if matches!(source, CoAuthorSource::Auto | CoAuthorSource::GitConfig) {
+            let from_config = git::config_get_all("git-ai.co-authors");
+            if !from_config.is_empty() || source == CoAuthorSource::GitConfig {
+                return from_config;
+            }
+        }
+
+        if matches!(source, CoAuthorSource::Auto | CoAuthorSource::PairingFile) {
+            if let Ok(path) = git::git_path("PAIRING") {
+                if let Ok(content) = std::fs::read_to_string(path) {
+                    return parse_pairing_file(&content);
+                }
+            }
+        }
+
+        Vec::new()
+    }
+
+    /// Build a style example from the last few commit messages, verbatim
+    fn style_anchor_context(&self, verbose: bool) -> Option<String> {
+        let count = self
+            .config
+            .style_anchor_commits
+            .filter(|&count| count > 0)?;
+
+        let messages = match git::recent_commit_messages(count) {
+            Ok(messages) if !messages.is_empty() => messages,
+            Ok(_) => return None,
+            Err(e) => {
+                if verbose {
+                    println!("{}Skipping style anchor commits: {}", icon(Icon::Warn), e);
+                }
+                return None;
+            }
+        };
+
+        Some(format!(
+            "Style anchor: these are the most recent commit messages in this repository, verbatim. \
+             Match their exact style (type and scope usage, capitalization, body layout, trailers) \
+             in the messages you write.\n\n{}",
+            messages.join("\n---\n")
+        ))
+    }
+
+    /// List TODO-style markers added by the pending changes
+    fn pending_markers_context(&self, verbose: bool) -> Option<String> {
+        let added_lines: Vec<(String, String)> = match git::pending_added_lines() {
+            Ok(lines) => lines
+                .into_iter()
+                .filter(|(path, line)| {
+                    // Minified lines would paste a whole bundle into the prompt
+                    !pattern::should_exclude(path)
+                        && line.chars().count() <= context::max_line_length()
+                })
+                .collect(),
+            Err(e) => {
+                if verbose {
+                    println!("{}Skipping pending marker scan: {}", icon(Icon::Warn), e);
+                }
+                return None;
+            }
+        };
+
+        match self.config.pending_markers {
+            Some(ref markers) => context::pending_markers_section(&added_lines, markers),
+            None => {
+                context::pending_markers_section(&added_lines, context::DEFAULT_PENDING_MARKERS)
+            }
+        }
+    }
+
+    /// Trailers that must be appended to every generated commit message
+    fn commit_trailers(&self, args: &CommitArgs) -> Result<Vec<String>> {
+        let mut trailers = Vec::new();
+
+        if !args.no_ticket {
+            if let Some(ticket) = context::branch_ticket(self.config.ticket_pattern.as_deref())? {
+                trailers.push(format!("Refs: {}", ticket));
+            }
+        }
+
+        trailers.extend(
+            self.co_authors()
+                .into_iter()
+                .map(|author| format!("Co-authored-by: {}", author)),
+        );
+
+        if let Some(ref footers) = self.config.footers {
+            let variables = [
+                ("user.name", git::config_get_all("user.name").pop()),
+                ("user.email", git::config_get_all("user.email").pop()),
+                ("branch", git::current_branch()),
+            ];
+            for footer in footers {
+                match expand_footer(footer, &variables) {
+                    Some(footer) => trailers.push(footer),
+                    None => println!(
+                        "{}Skipping footer with a value that is not available: {}",
+                        icon(Icon::Warn),
+                        footer
+                    ),
+                }
+            }
+        }
+
+        if self.config.generated_by_trailer.unwrap_or(false) {
+            trailers.push(GENERATED_BY_TRAILER.to_string());
+        }
+
+        // DCO sign-off for the configured git identity
+        if args.signoff {
+            let name = git::config_get_all("user.name").pop();
+            let email = git::config_get_all("user.email").pop();
+            let (Some(name), Some(email)) = (name, email) else {
+                anyhow::bail!("--signoff needs user.name and user.email set in git config");
+            };
+            trailers.push(format!("Signed-off-by: {} <{}>", name, email));
+        }
+
+        Ok(trailers)
+    }
+
+    /// Whether to ask for a subject-only message, either explicitly or
+    /// because the pending change is within the configured threshold
+    fn wants_oneline(&self, args: &CommitArgs) -> bool {
+        if args.oneline {
+            return true;
+        }
+
+        let Some(threshold) = self.config.oneline_threshold else {
+            return false;
+        };
+
+        match git::pending_change_lines() {
+            Ok(lines) => lines > 0 && lines <= threshold,
+            Err(e) => {
+                if args.common.verbose {
+                    println!("{}Skipping diff size check: {}", icon(Icon::Warn), e);
+                }
+                false
+            }
+        }
+    }
+
+    /// Ask the agent for its commit plan without committing and print it as JSON
+    async fn print_plan(
+        &self,
+        prompt: &str,
+        grouping: CommitGrouping,
+        agent: &CursorAgent,
+    ) -> Result<()> {
+        let status = git::repository_status().context("Failed to read repository status")?;
+        let plan = if status.is_clean() {
+            Vec::new()
+        } else {
+            let prompt = format!("{}\n\n{}", prompt, PLAN_OUTPUT_FORMAT);
+            let output = agent.capture(&prompt).await?;
+            parse_commit_plan(&output, grouping)
+                .and_then(|plan| {
+                    let allowed = allowed_types(&self.config);
+                    plan.iter()
+                        .try_for_each(|commit| check_commit_type(&commit.message, &allowed))?;
+                    Ok(plan)
+                })
+                .map_err(|e| agent.raw_response_error("commit-plan", &output, e))?
+        };
+
+        let json =
+            serde_json::to_string_pretty(&plan).context("Failed to serialize commit plan")?;
+        println!("{}", json);
+        Ok(())
+    }
+
+    /// Ask the agent for a single commit message without committing
+    async fn generate_message(
+        &self,
+        prompt: &str,
+        allow_empty: bool,
+        agent: &CursorAgent,
+    ) -> Result<String> {
+        let status = git::repository_status().context("Failed to read repository status")?;
+        if !allow_empty && !status.has_staged() && !status.has_unstaged() {
+            anyhow::bail!("No changes detected - nothing to commit");
+        }
+
+        let output = agent.capture(prompt).await?;
+        let message = clean_message(&output)
+            .and_then(|message| {
+                check_commit_type(&message, &allowed_types(&self.config))?;
+                Ok(message)
+            })
+            .map_err(|e| agent.raw_response_error("commit-message", &output, e))?;
+        if self.config.spellcheck.unwrap_or(false) {
+            spellcheck::warn(&message);
+        }
+        Ok(message)
+    }
+
+    /// Save a drafted message to `.git/git-ai/COMMIT_DRAFT` and show how to
+    /// commit it, for `auto_commit: false`
+    async fn draft_message(
+        &self,
+        prompt: &str,
+        args: &CommitArgs,
+        agent: &CursorAgent,
+    ) -> Result<()> {
+        let message = self
+            .generate_message(prompt, args.allow_empty, agent)
+            .await?;
+
+        let path = git::git_path("git-ai/COMMIT_DRAFT")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+        std::fs::write(&path, format!("{}\n", message))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        println!("{}Draft commit message:", icon(Icon::Working));
+        println!("{}", terminal::render_message(&message));
+        println!();
+        println!(
+            "{}Nothing was committed (auto_commit is off). Review and commit it with:",
+            icon(Icon::Hint)
+        );
+        println!(
+            "   git commit{} -e -F {}",
+            if args.allow_empty {
+                " --allow-empty"
+            } else {
+                ""
+            },
+            path.display()
+        );
+        Ok(())
+    }
+
+    /// Files the commit would include that are probably there by mistake: larger
+    /// than the configured limit, or binary and not stored with Git LFS
+    fn suspicious_files(&self, only_files: Option<&[String]>) -> Result<Vec<String>> {
+        let mut files = git::pending_files()?;
+        if let Some(only) = only_files {
+            files.retain(|(path, _)| only.contains(path));
+        }
+
+        let paths: Vec<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+        let lfs = git::lfs_tracked(&paths)?;
+        let root = git::repo_root()?;
+        let limit = self.config.large_file_mb.unwrap_or(DEFAULT_LARGE_FILE_MB) * 1024 * 1024;
+        let warn_binary = self.config.warn_binary_files.unwrap_or(true);
+
+        Ok(files
+            .iter()
+            .filter(|(path, _)| !lfs.contains(path))
+            .filter_map(|(path, binary)| {
+                // Deleted files add nothing to the repository
+                let size = std::fs::metadata(root.join(path)).ok()?.len();
+                describe_suspicious_file(path, size, *binary && warn_binary, limit)
+            })
+            .collect())
+    }
+
+    /// List suspicious files and ask before committing them (just warn with
+    /// `--no-confirm`). Returns `false` if the commit should not proceed.
+    fn confirm_suspicious_files(
+        &self,
+        args: &CommitArgs,
+        only_files: Option<&[String]>,
+    ) -> Result<bool> {
+        let files = match self.suspicious_files(only_files) {
+            Ok(files) => files,
+            Err(e) => {
+                if args.common.verbose {
+                    println!("{}Skipping large file check: {}", icon(Icon::Warn), e);
+                }
+                return Ok(true);
+            }
+        };
+        if files.is_empty() {
+            return Ok(true);
+        }
+
+        println!(
+            "{}These files are usually committed by mistake:",
+            icon(Icon::Warn)
+        );
+        for file in &files {
+            println!("   {}", file);
+        }
+        println!();
+
+        if args.no_confirm {
+            return Ok(true);
+        }
+
+        if !terminal::confirm("Commit them anyway?")? {
+            println!(
+                "{}Commit cancelled - unstage them with `git restore --staged <file>`",
+                icon(Icon::Error)
+            );
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    /// Run the agent against a temporary index holding only the staged changes
+    /// to `files`, so other staged changes stay out of the commit. A pathspec
+    /// commit would take the working-tree content of `files` instead.
+    async fn execute_only(
+        &self,
+        prompt: &str,
+        files: &[String],
+        args: &CommitArgs,
+        agent: &CursorAgent,
+    ) -> Result<()> {
+        let index = git::absolute_git_path("git-ai/ONLY_INDEX")?;
+        if let Some(dir) = index.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+
+        git::write_partial_index(&index, files)?;
+        let index_env = index.to_string_lossy().into_owned();
+        let result = agent
+            .execute_with_env(prompt, args.no_confirm, &[("GIT_INDEX_FILE", &index_env)])
+            .await;
+        let _ = std::fs::remove_file(&index);
+        result
+    }
+
+    /// Verify there is something to commit, offering to stage untracked files
+    /// when they are the only changes. Returns `false` if the commit should not proceed.
+    fn ensure_changes(&self, args: &CommitArgs) -> Result<bool> {
+        let status = git::repository_status().context("Failed to read repository status")?;
+
+        if status.is_clean() {
+            println!(
+                "{}No changes detected - nothing to commit",
+                icon(Icon::Info)
+            );
+            return Ok(false);
+        }
+
+        if status.has_staged() || status.has_unstaged() {
+            return Ok(true);
+        }
+
+        let untracked = status.untracked();
+        println!("{}Only untracked files were found:", icon(Icon::Files));
+        for file in &untracked {
+            println!("   {}", file.path);
+        }
+        println!();
+
+        if args.no_confirm {
+            println!(
+                "{}Stage the files you want to commit with `git add` and run again",
+                icon(Icon::Hint)
+            );
+            return Ok(false);
+        }
+
+        if !terminal::confirm("Stage these files and include them in the commit?")? {
+            println!("{}Nothing staged - aborting commit", icon(Icon::Error));
+            return Ok(false);
+        }
+
+        let paths: Vec<&str> = untracked.iter().map(|f| f.path.as_str()).collect();
+        git::add(&paths)?;
+        println!("{}Staged {} untracked file(s)", icon(Icon::Ok), paths.len());
+
+        Ok(true)
+    }
 }
 
 impl Command for CommitCommand {
@@ -76,19 +520,653 @@ impl Command for CommitCommand {
         // Use the template with custom message if provided
         let mut prompt = self.prompt_template().to_string();
 
+        let only_files = if args.only.is_empty() {
+            None
+        } else {
+            let files = git::staged_files(&args.only)?;
+            if files.is_empty() {
+                anyhow::bail!("No staged changes match --only {}", args.only.join(" "));
+            }
+            Some(files)
+        };
+
+        // Without auto-commit the AI only drafts a message, as with --message-only
+        let draft = args.message_only || !self.config.auto_commit.unwrap_or(true);
+
+        // A scoped, empty, or drafted commit is always a single commit
+        let grouping = if only_files.is_some() || draft || args.allow_empty {
+            CommitGrouping::Single
+        } else {
+            self.config.grouping.unwrap_or_default()
+        };
+        prompt = format!("{}\n\n{}", prompt, grouping_policy(grouping));
+
+        if let Some(ref files) = only_files {
+            prompt = format!("{}\n\n{}", prompt, only_scope(files, &git::repo_root()?));
+        }
+
+        if args.allow_empty {
+            if git::repository_status()?.has_staged() {
+                anyhow::bail!(
+                    "--allow-empty would also commit the staged changes; unstage them first"
+                );
+            }
+            prompt = format!("{}\n\n{}", prompt, EMPTY_COMMIT_GUIDANCE);
+        }
+
+        prompt = format!("{}\n\n{}", prompt, allowed_types_policy(&self.config));
+
+        if self.config.language_hints.unwrap_or(true) {
+            if let Some(hint) = language::language_hint_section() {
+                prompt = format!("{}\n\n{}", prompt, hint);
+            }
+        }
+
+        if self.wants_oneline(&args) {
+            prompt = format!("{}\n\n{}", prompt, ONELINE_GUIDANCE);
+        } else {
+            prompt = format!("{}\n\n{}", prompt, body_policy(&self.config));
+        }
+
+        if !git::has_commits() {
+            prompt = format!("{}\n\n{}", prompt, INITIAL_COMMIT_GUIDANCE);
+        } else if let Some(ref rev) = args.template_from_commit {
+            prompt = format!("{}\n\n{}", prompt, template_commit_context(rev)?);
+        } else if let Some(style_anchor) = self.style_anchor_context(args.common.verbose) {
+            prompt = format!("{}\n\n{}", prompt, style_anchor);
+        }
+
+        let hooks = if args.no_verify {
+            prompt = format!(
+                "{}\n\nCommit hooks: run every `git commit` with `--no-verify` so repository hooks are skipped.",
+                prompt
+            );
+            Vec::new()
+        } else {
+            git::commit_hooks()
+        };
+        if !hooks.is_empty() {
+            prompt = format!(
+                "{}\n\nCommit hooks: this repository runs these hooks on `git commit`: {}. They may rewrite \
+                 or reject commit messages. If a commit is rejected, read the hook output, fix the \
+                 message or changes it complains about, and retry. Do not bypass hooks with `--no-verify`.",
+                prompt,
+                hooks.join(", ")
+            );
+        }
+
+        if let Some(partial) = partially_staged_context(only_files.as_deref())? {
+            prompt = format!("{}\n\n{}", prompt, partial);
+        }
+
+        // Unstaged edits are not part of an empty commit
+        if !args.allow_empty {
+            if let Some(markers) = self.pending_markers_context(args.common.verbose) {
+                prompt = format!("{}\n\n{}", prompt, markers);
+            }
+        }
+
+        if args.stat {
+            if let Some(stat) = context::diffstat_section(&git::pending_diff_stat()?) {
+                prompt = format!("{}\n\n{}", prompt, stat);
+            }
+        }
+
+        match self.config.staged_content_kb {
+            Some(limit_kb) if limit_kb > 0 && !args.allow_empty => {
+                if let Some(staged) = staged_content_context(limit_kb, only_files.as_deref())? {
+                    prompt = format!("{}\n\n{}", prompt, staged);
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(included) = context::included_files_section(&args.include)? {
+            prompt = format!("{}\n\n{}", prompt, included);
+        }
+
+        if let Some(tone) = context::tone_section(self.config.tone.as_deref()) {
+            prompt = format!("{}\n\n{}", prompt, tone);
+        }
+
+        if let Some(always) = context::always_included_section() {
+            prompt = format!("{}\n\n{}", prompt, always);
+        }
+
         if let Some(ref message) = args.common.message {
             prompt = format!("{}\n\nUser context: {}", prompt, message);
         }
 
+        // Trailers go last so the provenance hash covers everything before them
+        let mut trailers = self.commit_trailers(&args)?;
+        if self.config.provenance_trailers.unwrap_or(false) {
+            trailers.extend(provenance_trailers(&prompt, agent)?);
+        }
+        if !trailers.is_empty() {
+            prompt = format!(
+                "{}\n\nRequired trailers: end every commit message with a blank line followed by these trailers, exactly as written:\n{}",
+                prompt,
+                trailers.join("\n")
+            );
+        }
+
+        if args.json {
+            return self.print_plan(&prompt, grouping, agent).await;
+        }
+
+        if draft {
+            prompt = format!("{}\n\n{}", prompt, MESSAGE_OUTPUT_FORMAT);
+        }
+
         if args.common.dry_run {
-            println!("🔍 Dry run mode - would execute with prompt:");
+            println!(
+                "{}Dry run mode - would execute with prompt:",
+                icon(Icon::Search)
+            );
             println!("---");
             println!("{}", prompt);
             println!("---");
             return Ok(());
         }
 
+        if args.message_only {
+            let message = self
+                .generate_message(&prompt, args.allow_empty, agent)
+                .await?;
+            println!("{}", message);
+            return Ok(());
+        }
+
+        if draft {
+            return self.draft_message(&prompt, &args, agent).await;
+        }
+
+        if !hooks.is_empty() {
+            println!(
+                "{}Commit hooks installed ({}) may change or reject the generated message; use --no-verify to skip them",
+                icon(Icon::Warn),
+                hooks.join(", ")
+            );
+        }
+
+        if !args.allow_empty {
+            if only_files.is_none() && !self.ensure_changes(&args)? {
+                return Ok(());
+            }
+
+            if !self.confirm_suspicious_files(&args, only_files.as_deref())? {
+                return Ok(());
+            }
+        }
+
         // Use shared cursor-agent service
-        agent.execute(&prompt, args.no_confirm).await
+        let head_before = git::head_commit();
+        match only_files {
+            Some(ref files) => self.execute_only(&prompt, files, &args, agent).await?,
+            None => agent.execute(&prompt, args.no_confirm).await?,
+        }
+        print_commit_summary(head_before.as_deref(), &allowed_types(&self.config));
+
+        Ok(())
+    }
+}
+
+/// Show the commits created since `head_before` (all commits if there was no
+/// HEAD), reporting any whose type is not in `allowed`. The agent has already
+/// committed them, so a violation is reported rather than rejected.
+fn print_commit_summary(head_before: Option<&str>, allowed: &[&str]) {
+    let head_after = git::head_commit();
+    if head_after.is_none() || head_after.as_deref() == head_before {
+        println!("{}No commits were created", icon(Icon::Info));
+        return;
+    }
+
+    let range = match head_before {
+        Some(before) => format!("{}..HEAD", before),
+        None => "HEAD".to_string(),
+    };
+    let commits = match git::commit_summaries(&range) {
+        Ok(commits) if !commits.is_empty() => commits,
+        _ => return,
+    };
+
+    println!("{}Created {} commit(s):", icon(Icon::Ok), commits.len());
+    for commit in &commits {
+        println!(
+            "   {} {} ({} file{}, +{} -{})",
+            commit.sha,
+            commit.subject,
+            commit.files,
+            if commit.files == 1 { "" } else { "s" },
+            commit.insertions,
+            commit.deletions
+        );
+    }
+
+    for commit in &commits {
+        if check_commit_type(&commit.subject, allowed).is_err() {
+            println!(
+                "{}{} uses a commit type outside allowed_types ({}); fix it with `git ai reword {}`",
+                icon(Icon::Warn),
+                commit.sha,
+                allowed.join(", "),
+                commit.sha
+            );
+        }
+    }
+}
+
+/// Prompt instruction listing the conventional commit types that may be used
+pub fn allowed_types_policy(config: &CommitConfig) -> String {
+    format!(
+        "Allowed commit types: {}. Use only these types in commit subjects; \
+         pick the closest match rather than inventing a new type.",
+        allowed_types(config).join(", ")
+    )
+}
+
+/// Configured commit types, or [`DEFAULT_COMMIT_TYPES`] if none are set
+fn allowed_types(config: &CommitConfig) -> Vec<&str> {
+    match config.allowed_types {
+        Some(ref types) if !types.is_empty() => types.iter().map(String::as_str).collect(),
+        _ => DEFAULT_COMMIT_TYPES.to_vec(),
+    }
+}
+
+/// Reject a message whose subject does not start with one of the `allowed`
+/// conventional-commit types, e.g. `feat` in `feat(api)!: add login`
+fn check_commit_type(message: &str, allowed: &[&str]) -> Result<()> {
+    let subject = message.lines().next().unwrap_or_default().trim();
+    let commit_type = subject
+        .split_once(':')
+        .map(|(prefix, _)| prefix.split(['(', '!']).next().unwrap_or_default().trim())
+        .unwrap_or_default();
+
+    if !allowed.contains(&commit_type) {
+        anyhow::bail!(
+            "cursor-agent used a commit type that is not allowed in \"{}\" (allowed: {})",
+            subject,
+            allowed.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Prompt instruction describing how long the commit message body may be
+pub fn body_policy(config: &CommitConfig) -> String {
+    let bullets = config.body_bullets.unwrap_or(DEFAULT_BODY_BULLETS);
+    if !config.include_body.unwrap_or(true) || bullets == 0 {
+        return "Message body: write only the subject line, with no body \
+                (required trailers, if any, still follow after a blank line)."
+            .to_string();
+    }
+
+    format!(
+        "Message body: after a blank line, summarize what changed and why in at most {} bullet point{}.",
+        bullets,
+        if bullets == 1 { "" } else { "s" }
+    )
+}
+
+/// One-shot example taken from the message of the `--template-from-commit` commit
+fn template_commit_context(rev: &str) -> Result<String> {
+    if !git::rev_exists(rev) {
+        anyhow::bail!("Not a commit: {}", rev);
+    }
+
+    if git::commit_parents(rev)?.len() > 1 {
+        // stderr keeps `--dry-run --json` output parseable
+        eprintln!(
+            "{}{} is a merge commit; its message may not make a good template",
+            icon(Icon::Warn),
+            rev
+        );
+    }
+
+    let message = git::commit_message(rev)?;
+    if message.trim().is_empty() {
+        anyhow::bail!("Commit {} has an empty message to use as a template", rev);
+    }
+
+    Ok(format!(
+        "Template commit: format every commit message exactly like this example (same layout, \
+         type and scope style, capitalization, body structure, and trailer placement), but \
+         describe the current changes rather than the example's.\n---\n{}\n---",
+        message
+    ))
+}
+
+/// Prompt instruction restricting the commit to the files selected by `--only`,
+/// whose paths are relative to the repository `root`. The agent runs against a
+/// narrowed index holding only their staged changes (see [`git::write_partial_index`]).
+fn only_scope(files: &[String], root: &Path) -> String {
+    let list = files
+        .iter()
+        .map(|file| format!("- {}", file))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "Commit scope: consider ONLY the staged changes to these files (relative to the repository \
+         root, {}) and ignore every other change:\n{}\n\n\
+         The index has been narrowed to exactly these staged changes, so `git diff --cached` shows \
+         them and a plain `git commit -m <message>` commits only them; other staged changes stay \
+         staged for a later commit. Do not pass paths to `git commit` (that would commit \
+         working-tree content instead of the staged content), and do not stage, unstage, or \
+         commit any other file.",
+        root.display(),
+        list
+    )
+}
+
+/// Prompt instruction describing the configured commit grouping policy
+fn grouping_policy(grouping: CommitGrouping) -> &'static str {
+    match grouping {
+        CommitGrouping::Single => {
+            "Commit grouping policy: single. Create exactly ONE commit containing all of the changes \
+             under consideration, even if they span several change types. Do not split them into \
+             multiple commits; choose the type and scope that best describe the change as a whole."
+        }
+        CommitGrouping::Atomic => {
+            "Commit grouping policy: atomic. Split the changes into separate commits, one per \
+             logical change, as described in the grouping guidance above."
+        }
+    }
+}
+
+/// Parse the JSON commit plan out of the AI output, ignoring any surrounding
+/// prose or code fence. A plan that splits a single-commit change is rejected.
+fn parse_commit_plan(output: &str, grouping: CommitGrouping) -> Result<Vec<ProposedCommit>> {
+    let start = output
+        .find('[')
+        .context("cursor-agent did not return a JSON commit plan")?;
+    let end = output
+        .rfind(']')
+        .filter(|&end| end > start)
+        .context("cursor-agent did not return a JSON commit plan")?;
+
+    let plan: Vec<ProposedCommit> = serde_json::from_str(&output[start..=end])
+        .context("cursor-agent returned an invalid JSON commit plan")?;
+    if plan.iter().any(|commit| commit.message.trim().is_empty()) {
+        anyhow::bail!("cursor-agent returned a commit plan with an empty message");
+    }
+    if grouping == CommitGrouping::Single && plan.len() > 1 {
+        anyhow::bail!(
+            "cursor-agent proposed {} commits, but this change must be a single commit",
+            plan.len()
+        );
+    }
+
+    Ok(plan)
+}
+
+/// Trim the agent's output to the bare commit message, dropping any code fence
+pub fn clean_message(output: &str) -> Result<String> {
+    let mut message = output.trim();
+
+    if let Some(fenced) = message.strip_prefix("```") {
+        // Drop the fence line (which may name a language) and the closing fence
+        message = fenced
+            .split_once('\n')
+            .map(|(_, rest)| rest)
+            .unwrap_or_default();
+        message = message
+            .trim_end()
+            .strip_suffix("```")
+            .unwrap_or(message)
+            .trim();
+    }
+
+    if message.is_empty() {
+        anyhow::bail!("cursor-agent did not return a commit message");
+    }
+
+    Ok(message.to_string())
+}
+
+/// Prompt section naming files that were staged and then edited again, so the
+/// staged and unstaged diffs of the same file are not described twice. Only
+/// files in `only_files` are named when it is given.
+fn partially_staged_context(only_files: Option<&[String]>) -> Result<Option<String>> {
+    let status = git::repository_status()?;
+    let paths: Vec<&str> = status
+        .partially_staged()
+        .iter()
+        .map(|f| f.path.as_str())
+        .filter(|path| only_files.map_or(true, |only| only.iter().any(|file| file == path)))
+        .collect();
+    Ok((!paths.is_empty()).then(|| partially_staged_section(&paths)))
+}
+
+/// Full staged content of the staged text files no larger than `limit_kb`,
+/// for the `staged_content_kb` setting. Deleted, binary, and minified files
+/// are skipped, as are files outside `only_files` when it is given.
+fn staged_content_context(limit_kb: u64, only_files: Option<&[String]>) -> Result<Option<String>> {
+    let mut paths = git::staged_files(&[])?;
+    if let Some(only) = only_files {
+        paths.retain(|path| only.contains(path));
+    }
+
+    let limit = limit_kb * 1024;
+    let files: Vec<(String, String)> = paths
+        .into_iter()
+        .filter(|path| !pattern::should_exclude(path))
+        // An empty revision names the index (`:<path>`)
+        .filter(|path| git::blob_size("", path).is_some_and(|size| size <= limit))
+        .filter_map(|path| {
+            let content = git::staged_content(&path).ok()?;
+            let readable = !content.contains('\0')
+                && content
+                    .lines()
+                    .all(|line| line.chars().count() <= context::max_line_length());
+            readable.then_some((path, content))
+        })
+        .take(context::MAX_STAGED_CONTENT_FILES)
+        .collect();
+
+    Ok(context::staged_content_section(&files))
+}
+
+fn partially_staged_section(paths: &[&str]) -> String {
+    format!(
+        "Partially staged files: these files have staged changes and further unstaged edits. \
+         `git diff --cached` and `git diff` show different, separate changes for them; only the \
+         staged part (`git diff --cached`) belongs in these commits. Do not describe the unstaged \
+         edits, count any change twice, or stage more of these files:\n{}",
+        paths
+            .iter()
+            .map(|path| format!("- {}", path))
+            .collect::<Vec<_>>()
+            .join("\n")
+    )
+}
+
+/// Why a file of `size` bytes should not be committed, if it exceeds `limit`
+/// (0 disables the size check) or is an unwanted binary
+fn describe_suspicious_file(path: &str, size: u64, binary: bool, limit: u64) -> Option<String> {
+    if limit > 0 && size > limit {
+        Some(format!(
+            "{} ({:.1} MB)",
+            path,
+            size as f64 / (1024.0 * 1024.0)
+        ))
+    } else if binary {
+        Some(format!("{} (binary, not stored with Git LFS)", path))
+    } else {
+        None
+    }
+}
+
+/// Fill `{name}` placeholders in a configured footer. Returns `None` if a
+/// placeholder refers to a value that is not available.
+fn expand_footer(template: &str, variables: &[(&str, Option<String>)]) -> Option<String> {
+    let mut footer = template.to_string();
+    for (name, value) in variables {
+        let placeholder = format!("{{{}}}", name);
+        if footer.contains(&placeholder) {
+            footer = footer.replace(&placeholder, value.as_deref()?);
+        }
+    }
+
+    let footer = footer.trim().to_string();
+    (!footer.is_empty()).then_some(footer)
+}
+
+/// Trailers recording the agent and prompt a commit was generated with
+fn provenance_trailers(prompt: &str, agent: &CursorAgent) -> Result<Vec<String>> {
+    let prompt_hash = git::hash_text(prompt).context("Failed to hash commit prompt")?;
+    let agent_version = agent.version().unwrap_or_else(|| "unknown".to_string());
+
+    Ok(vec![
+        format!("Git-AI-Agent: cursor-agent {}", agent_version),
+        format!(
+            "Git-AI-Prompt-Hash: {}",
+            &prompt_hash[..12.min(prompt_hash.len())]
+        ),
+    ])
+}
+
+/// Parse co-authors from a pairing file, one `Name <email>` per line
+fn parse_pairing_file(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.strip_prefix("Co-authored-by:")
+                .map(str::trim)
+                .unwrap_or(line)
+                .to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_policy() {
+        let default = body_policy(&CommitConfig::default());
+        assert!(default.contains("at most 2 bullet points"));
+
+        let one = body_policy(&CommitConfig {
+            body_bullets: Some(1),
+            ..CommitConfig::default()
+        });
+        assert!(one.contains("at most 1 bullet point."));
+
+        let none = body_policy(&CommitConfig {
+            include_body: Some(false),
+            ..CommitConfig::default()
+        });
+        assert!(none.contains("only the subject line"));
+    }
+
+    #[test]
+    fn test_parse_pairing_file() {
+        let content = "# current pair\nAda Lovelace <ada@example.com>\n\nCo-authored-by: Alan Turing <alan@example.com>\n";
+        assert_eq!(
+            parse_pairing_file(content),
+            vec![
+                "Ada Lovelace <ada@example.com>".to_string(),
+                "Alan Turing <alan@example.com>".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_plan() {
+        let output = "Here is the plan:\n```json\n[\n  {\"message\": \"feat(api): add login\\n\\n- add route\", \"files\": [\"src/api.rs\"]},\n  {\"message\": \"docs: update readme\"}\n]\n```\n";
+        let plan = parse_commit_plan(output, CommitGrouping::Atomic).unwrap();
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].message, "feat(api): add login\n\n- add route");
+        assert_eq!(plan[0].files, vec!["src/api.rs"]);
+        assert!(plan[1].files.is_empty());
+
+        assert!(parse_commit_plan(output, CommitGrouping::Single).is_err());
+        assert_eq!(
+            parse_commit_plan(
+                "[{\"message\": \"feat: add login\"}]",
+                CommitGrouping::Single
+            )
+            .unwrap()
+            .len(),
+            1
+        );
+
+        assert!(parse_commit_plan("feat: no json here", CommitGrouping::Atomic).is_err());
+        assert!(parse_commit_plan("[{\"message\": \"  \"}]", CommitGrouping::Atomic).is_err());
+    }
+
+    #[test]
+    fn test_check_commit_type() {
+        let allowed = ["feat", "fix"];
+        assert!(check_commit_type("feat: add login\n\n- add route", &allowed).is_ok());
+        assert!(check_commit_type("fix(auth)!: drop legacy tokens", &allowed).is_ok());
+        assert!(check_commit_type("docs: update readme", &allowed).is_err());
+        assert!(check_commit_type("Update readme", &allowed).is_err());
+        assert!(check_commit_type("feature: add login", &allowed).is_err());
+    }
+
+    #[test]
+    fn test_expand_footer() {
+        let variables = [
+            ("user.name", Some("Ada Lovelace".to_string())),
+            ("user.email", Some("ada@example.com".to_string())),
+            ("branch", None),
+        ];
+
+        assert_eq!(
+            expand_footer("Reviewed-by: {user.name} <{user.email}>", &variables).as_deref(),
+            Some("Reviewed-by: Ada Lovelace <ada@example.com>")
+        );
+        assert_eq!(
+            expand_footer("Team: platform", &variables).as_deref(),
+            Some("Team: platform")
+        );
+        assert_eq!(expand_footer("Branch: {branch}", &variables), None);
+        assert_eq!(expand_footer("  ", &variables), None);
+    }
+
+    #[test]
+    fn test_partially_staged_section() {
+        let section = partially_staged_section(&["src/lib.rs", "README.md"]);
+        assert!(section.contains("only the staged part"));
+        assert!(section.ends_with("- src/lib.rs\n- README.md"));
+    }
+
+    #[test]
+    fn test_clean_message() {
+        assert_eq!(
+            clean_message("\nfix(auth): reject expired tokens\n\n- check exp claim\n").unwrap(),
+            "fix(auth): reject expired tokens\n\n- check exp claim"
+        );
+        assert_eq!(
+            clean_message("```text\nfeat: add login\n```\n").unwrap(),
+            "feat: add login"
+        );
+        assert!(clean_message("  \n").is_err());
+    }
+
+    #[test]
+    fn test_describe_suspicious_file() {
+        let limit = 5 * 1024 * 1024;
+        assert_eq!(
+            describe_suspicious_file("dump.sql", 200 * 1024 * 1024, false, limit).as_deref(),
+            Some("dump.sql (200.0 MB)")
+        );
+        assert_eq!(
+            describe_suspicious_file("logo.psd", 1024, true, limit).as_deref(),
+            Some("logo.psd (binary, not stored with Git LFS)")
+        );
+        assert_eq!(
+            describe_suspicious_file("src/lib.rs", 1024, false, limit),
+            None
+        );
+        assert_eq!(
+            describe_suspicious_file("dump.sql", 200 * 1024 * 1024, false, 0),
+            None
+        );
     }
 }
@@ -0,0 +1,138 @@
+use crate::cli::args::CompletionsArgs;
+use crate::commands::Command;
+use crate::cursor_agent::CursorAgent;
+use crate::terminal::{icon, Icon};
+use crate::Cli;
+use anyhow::{Context, Result};
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::path::{Path, PathBuf};
+
+/// Name completions are generated for
+const BIN_NAME: &str = "git-ai";
+
+/// Completions command implementation (no prompt needed)
+pub struct CompletionsCommand;
+
+impl CompletionsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Command for CompletionsCommand {
+    type Args = CompletionsArgs;
+    type Config = (); // Completions command doesn't need config
+
+    fn prompt_template(&self) -> &str {
+        "" // No prompt for completions command
+    }
+
+    fn resolve_args(&self, args: CompletionsArgs) -> CompletionsArgs {
+        // No overrides for completions command
+        args
+    }
+
+    async fn execute(&self, args: CompletionsArgs, _agent: &CursorAgent) -> Result<()> {
+        let shell = match args.shell.or_else(Shell::from_env) {
+            Some(shell) => shell,
+            None => anyhow::bail!("Could not detect your shell from $SHELL; pass --shell"),
+        };
+
+        let mut script = Vec::new();
+        clap_complete::generate(shell, &mut Cli::command(), BIN_NAME, &mut script);
+
+        if !args.install {
+            std::io::Write::write_all(&mut std::io::stdout(), &script)
+                .context("Failed to write completions")?;
+            return Ok(());
+        }
+
+        let home = dirs::home_dir().context("Could not determine your home directory")?;
+        let path = install_path(
+            shell,
+            &home,
+            std::env::var_os("XDG_DATA_HOME").map(PathBuf::from),
+            std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from),
+        )
+        .with_context(|| {
+            format!(
+                "Installing {} completions is not supported; save the output of \
+                 `git ai completions --shell {}` where your shell loads completions",
+                shell, shell
+            )
+        })?;
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+        std::fs::write(&path, script)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        println!(
+            "{}Installed {} completions to {}",
+            icon(Icon::Saved),
+            shell,
+            path.display()
+        );
+        if shell == Shell::Zsh {
+            println!(
+                "{}Make sure ~/.zshrc adds the directory before compinit: fpath=(~/.zfunc $fpath)",
+                icon(Icon::Hint)
+            );
+        }
+        println!("{}Open a new shell to start using them", icon(Icon::Hint));
+
+        Ok(())
+    }
+}
+
+/// Where `shell` loads user completion scripts from, or `None` if it has no
+/// conventional per-user location
+fn install_path(
+    shell: Shell,
+    home: &Path,
+    xdg_data_home: Option<PathBuf>,
+    xdg_config_home: Option<PathBuf>,
+) -> Option<PathBuf> {
+    match shell {
+        Shell::Bash => Some(
+            xdg_data_home
+                .unwrap_or_else(|| home.join(".local/share"))
+                .join("bash-completion/completions")
+                .join(BIN_NAME),
+        ),
+        Shell::Zsh => Some(home.join(".zfunc").join(format!("_{}", BIN_NAME))),
+        Shell::Fish => Some(
+            xdg_config_home
+                .unwrap_or_else(|| home.join(".config"))
+                .join("fish/completions")
+                .join(format!("{}.fish", BIN_NAME)),
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_path() {
+        let home = Path::new("/home/ada");
+        assert_eq!(
+            install_path(Shell::Bash, home, None, None).unwrap(),
+            Path::new("/home/ada/.local/share/bash-completion/completions/git-ai")
+        );
+        assert_eq!(
+            install_path(Shell::Zsh, home, None, None).unwrap(),
+            Path::new("/home/ada/.zfunc/_git-ai")
+        );
+        assert_eq!(
+            install_path(Shell::Fish, home, None, Some(PathBuf::from("/xdg"))).unwrap(),
+            Path::new("/xdg/fish/completions/git-ai.fish")
+        );
+        assert_eq!(install_path(Shell::PowerShell, home, None, None), None);
+    }
+}
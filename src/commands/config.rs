@@ -1,16 +1,19 @@
 use crate::cli::args::ConfigArgs;
 use crate::commands::Command;
-use crate::config::Config;
+use crate::config::{Config, CONFIG_VERSION, REPO_CONFIG_FILE};
 use crate::cursor_agent::CursorAgent;
-use anyhow::Result;
+use crate::terminal::{icon, Icon};
+use anyhow::{Context, Result};
 use std::path::PathBuf;
 
 /// Config command implementation (no prompt needed)
-pub struct ConfigCommand;
+pub struct ConfigCommand {
+    config: Config,
+}
 
 impl ConfigCommand {
-    pub fn new() -> Self {
-        Self
+    pub fn new(config: Config) -> Self {
+        Self { config }
     }
 }
 
@@ -29,14 +32,52 @@ impl Command for ConfigCommand {
 
     async fn execute(&self, args: ConfigArgs, _agent: &CursorAgent) -> Result<()> {
         // Config command doesn't need cursor-agent
-        self.handle_config(args.show, args.init)
+        self.handle_config(args)
     }
 }
 
 impl ConfigCommand {
     /// Handle the config command logic
-    fn handle_config(&self, show: bool, init: bool) -> Result<()> {
-        if init {
+    fn handle_config(&self, args: ConfigArgs) -> Result<()> {
+        if args.migrate {
+            return self.migrate_configs();
+        }
+
+        if let Some((ref key, ref value)) = args.set {
+            let path = Self::target_path(args.global)?;
+            Config::set_value(&path, key, value)?;
+            println!(
+                "{}Set {} = {} in {}",
+                icon(Icon::Ok),
+                key,
+                value,
+                path.display()
+            );
+            return Ok(());
+        }
+
+        if let Some(ref key) = args.unset {
+            let path = Self::target_path(args.global)?;
+            if Config::unset_value(&path, key)? {
+                println!("{}Unset {} in {}", icon(Icon::Ok), key, path.display());
+            } else {
+                println!(
+                    "{}{} is not set in {}",
+                    icon(Icon::Info),
+                    key,
+                    path.display()
+                );
+            }
+            return Ok(());
+        }
+
+        if args.effective {
+            println!("# Effective git-ai configuration (defaults + user + repository)");
+            println!("{}", serde_yaml::to_string(&self.config)?);
+            return Ok(());
+        }
+
+        if args.init {
             let sample_config = Config::create_sample_config()?;
             println!("# Sample git-ai configuration");
             println!("# Copy this to ~/.config/git-ai/config.yaml or .git-ai.yaml");
@@ -45,37 +86,61 @@ impl ConfigCommand {
             return Ok(());
         }
 
-        if show {
-            println!("🔍 git-ai configuration status:");
+        if args.show {
+            println!("{}git-ai configuration status:", icon(Icon::Search));
             println!();
 
-            // Check for repo-specific config
-            let repo_config_path = PathBuf::from(".git-ai.yaml");
-            if repo_config_path.exists() {
-                println!("✅ Repository config: .git-ai.yaml");
-            } else {
-                println!("❌ Repository config: .git-ai.yaml (not found)");
+            // Check for repo-specific configs, from the repo root down
+            let repo_config_paths: Vec<PathBuf> = Config::repo_config_paths()
+                .into_iter()
+                .filter(|path| path.exists())
+                .collect();
+            if repo_config_paths.is_empty() {
+                println!(
+                    "{}Repository config: {} (not found)",
+                    icon(Icon::Error),
+                    REPO_CONFIG_FILE
+                );
+            }
+            for path in &repo_config_paths {
+                println!("{}Repository config: {}", icon(Icon::Ok), path.display());
             }
 
             // Check for user config
             if let Some(user_config_path) = Config::user_config_path() {
                 if user_config_path.exists() {
-                    println!("✅ User config: {}", user_config_path.display());
+                    println!(
+                        "{}User config: {}",
+                        icon(Icon::Ok),
+                        user_config_path.display()
+                    );
                 } else {
-                    println!("❌ User config: {} (not found)", user_config_path.display());
+                    println!(
+                        "{}User config: {} (not found)",
+                        icon(Icon::Error),
+                        user_config_path.display()
+                    );
                     if let Some(parent) = user_config_path.parent() {
                         if !parent.exists() {
-                            println!("   💡 Create directory: mkdir -p {}", parent.display());
+                            println!(
+                                "   {}Create directory: mkdir -p {}",
+                                icon(Icon::Hint),
+                                parent.display()
+                            );
                         }
                     }
                 }
             } else {
-                println!("❌ User config: Unable to determine config directory");
+                println!(
+                    "{}User config: Unable to determine config directory",
+                    icon(Icon::Error)
+                );
             }
 
             println!();
             println!(
-                "💡 To create a sample config: git ai config --init > ~/.config/git-ai/config.yaml"
+                "{}To create a sample config: git ai config --init > ~/.config/git-ai/config.yaml",
+                icon(Icon::Hint)
             );
 
             return Ok(());
@@ -87,12 +152,73 @@ impl ConfigCommand {
         println!("Options:");
         println!("  --show  Show current configuration status");
         println!("  --init  Generate sample configuration");
+        println!("  --migrate  Upgrade existing config files to the current format");
+        println!("  --effective  Print the resolved configuration after merging");
+        println!("  --set <KEY> <VALUE>  Set a setting (add --global for the user config)");
+        println!("  --unset <KEY>  Remove a setting so its default applies");
         println!();
         println!("Examples:");
         println!("  git ai config --show");
+        println!("  git ai config --effective");
+        println!("  git ai config --set commands.commit.tone terse");
         println!("  git ai config --init > ~/.config/git-ai/config.yaml");
         println!("  git ai config --init > .git-ai.yaml  # Repository-specific config");
 
         Ok(())
     }
+
+    /// Config file edited by --set/--unset: the user config with --global,
+    /// otherwise the closest existing .git-ai.yaml (or the repository root's)
+    fn target_path(global: bool) -> Result<PathBuf> {
+        if global {
+            return Config::user_config_path()
+                .context("Unable to determine the user config directory");
+        }
+
+        let paths = Config::repo_config_paths();
+        let closest = paths.iter().rev().find(|path| path.exists());
+        Ok(closest
+            .or(paths.first())
+            .cloned()
+            .unwrap_or_else(|| PathBuf::from(REPO_CONFIG_FILE)))
+    }
+
+    /// Migrate the repository and user config files to the current format
+    fn migrate_configs(&self) -> Result<()> {
+        let mut paths = Config::repo_config_paths();
+        if let Some(user_config_path) = Config::user_config_path() {
+            paths.push(user_config_path);
+        }
+
+        let mut found = false;
+        for path in paths.iter().filter(|path| path.exists()) {
+            found = true;
+            match Config::migrate_file(path)? {
+                Some(from_version) => {
+                    println!(
+                        "{}Migrated {} from version {} to {}",
+                        icon(Icon::Ok),
+                        path.display(),
+                        from_version,
+                        CONFIG_VERSION
+                    );
+                    println!(
+                        "   {}Original saved to {}.bak",
+                        icon(Icon::Saved),
+                        path.display()
+                    );
+                }
+                None => println!("{}{} is already up to date", icon(Icon::Ok), path.display()),
+            }
+        }
+
+        if !found {
+            println!(
+                "{}No config files found - nothing to migrate",
+                icon(Icon::Info)
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -1,8 +1,13 @@
 use crate::cli::args::IgnoreArgs;
 use crate::commands::Command;
 use crate::config::IgnoreConfig;
+use crate::context;
 use crate::cursor_agent::CursorAgent;
+use crate::git;
+use crate::gitignore::{self, GitignoreManager};
+use crate::terminal::{icon, Icon};
 use anyhow::Result;
+use std::path::PathBuf;
 
 /// AI-assisted .gitignore management prompt
 const IGNORE_PROMPT: &str = r#"You are operating inside a command line interface as an AI assistant integrated with Git via `cursor-agent`.
@@ -71,6 +76,85 @@ impl IgnoreCommand {
     pub fn new(config: IgnoreConfig) -> Self {
         Self { config }
     }
+
+    /// Add or remove the sections git-ai can handle itself: built-in templates
+    /// for `add`, and any existing marker section for `remove`. Returns the
+    /// languages left for the AI.
+    fn apply_sections(&self, args: &IgnoreArgs) -> Result<Vec<String>> {
+        let path = gitignore_path();
+        let mut manager = GitignoreManager::load(&path)?;
+        let mut changes = Vec::new();
+        let mut remaining = Vec::new();
+
+        for language in &args.languages {
+            let template = gitignore::template(language);
+            let section = template.map_or(language.as_str(), |(section, _)| section);
+
+            if args.action == "add" {
+                let Some((_, patterns)) = template else {
+                    remaining.push(language.clone());
+                    continue;
+                };
+                if manager.add_section(section, patterns) {
+                    changes.push((true, section));
+                } else {
+                    println!(
+                        "{}.gitignore already has a {} section",
+                        icon(Icon::Info),
+                        section
+                    );
+                }
+            } else if manager.remove_section(section) {
+                changes.push((false, section));
+            } else if template.is_some() {
+                println!("{}.gitignore has no {} section", icon(Icon::Info), section);
+            } else {
+                remaining.push(language.clone());
+            }
+        }
+
+        if changes.is_empty() {
+            return Ok(remaining);
+        }
+
+        if args.dry_run {
+            println!(
+                "{}Dry run mode - would update {}:",
+                icon(Icon::Search),
+                path.display()
+            );
+            for (added, section) in &changes {
+                let action = if *added { "Add" } else { "Remove" };
+                println!("   {} {} section", action, section);
+            }
+        } else {
+            manager.save(&path)?;
+            for (added, section) in &changes {
+                let (action, preposition) = if *added {
+                    ("Added", "to")
+                } else {
+                    ("Removed", "from")
+                };
+                println!(
+                    "{}{} {} section {} .gitignore",
+                    icon(Icon::Ok),
+                    action,
+                    section,
+                    preposition
+                );
+            }
+        }
+
+        Ok(remaining)
+    }
+}
+
+/// The repository's top-level `.gitignore`, or the one in the current
+/// directory outside a repository
+fn gitignore_path() -> PathBuf {
+    git::repo_root()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(".gitignore")
 }
 
 impl Command for IgnoreCommand {
@@ -91,21 +175,41 @@ impl Command for IgnoreCommand {
     }
 
     async fn execute(&self, args: IgnoreArgs, agent: &CursorAgent) -> Result<()> {
+        // Known sections are edited directly; the AI handles everything else
+        let languages = self.apply_sections(&args)?;
+        if languages.is_empty() && !args.languages.is_empty() {
+            return Ok(());
+        }
+
         let mut prompt = self.prompt_template().to_string();
 
         // Add action context
         prompt = format!("{}\n\nAction: {}", prompt, args.action);
 
         // Add languages context
-        if !args.languages.is_empty() {
-            let languages_str = args.languages.join(", ");
+        if !languages.is_empty() {
+            let languages_str = languages.join(", ");
             prompt = format!("{}\n\nLanguages/Tools: {}", prompt, languages_str);
         }
 
+        let sections = GitignoreManager::load(&gitignore_path())?.list_sections();
+        if !sections.is_empty() {
+            prompt = format!(
+                "{}\n\nExisting .gitignore sections: {}",
+                prompt,
+                sections.join(", ")
+            );
+        }
+
+        if let Some(always) = context::always_included_section() {
+            prompt = format!("{}\n\n{}", prompt, always);
+        }
+
         // Handle dry run
         if args.dry_run {
             println!(
-                "🔍 Dry run mode - would execute with prompt:\n---\n{}\n---",
+                "{}Dry run mode - would execute with prompt:\n---\n{}\n---",
+                icon(Icon::Search),
                 prompt
             );
             return Ok(());
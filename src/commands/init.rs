@@ -1,7 +1,9 @@
 use crate::cli::args::InitArgs;
 use crate::commands::Command;
 use crate::config::InitConfig;
+use crate::context;
 use crate::cursor_agent::CursorAgent;
+use crate::terminal::{icon, Icon};
 use anyhow::Result;
 
 /// AI-assisted project initialization prompt
@@ -54,6 +56,46 @@ Your goal is to **initialize a new project repository** based on the user's chos
 
 When ready, proceed with creating the repository as described above."#;
 
+/// Appended in non-interactive mode so the AI acts on fixed decisions
+const NON_INTERACTIVE_INSTRUCTIONS: &str = "Non-interactive mode: this runs in automation and \
+     nobody can answer questions. Do NOT ask the user anything or wait for confirmation. Use the \
+     fixed decisions below exactly as given, pick sensible defaults for anything they do not \
+     cover (linter, formatter, test framework, pre-commit hooks), and scaffold the whole project.";
+
+/// The fixed scaffolding decisions for non-interactive mode
+fn fixed_decisions(args: &InitArgs) -> String {
+    let mut decisions = vec![format!(
+        "- Language: {}",
+        args.language.as_deref().unwrap_or_default()
+    )];
+    decisions.push(match args.name {
+        Some(ref name) => format!("- Project name: {}", name),
+        None => "- Project name: the name of the current directory".to_string(),
+    });
+    decisions.push(match args.package_manager {
+        Some(ref package_manager) => format!("- Package manager: {}", package_manager),
+        None => "- Package manager: the language's standard tool".to_string(),
+    });
+    decisions.push(format!(
+        "- GitHub Actions CI workflow: {}",
+        if args.ci { "yes" } else { "no" }
+    ));
+    decisions.push(match args.license {
+        Some(ref license) => format!("- License: {}", license),
+        None => "- License: none".to_string(),
+    });
+    decisions.push(format!(
+        "- README: {}",
+        if args.no_readme { "no" } else { "yes" }
+    ));
+
+    format!(
+        "{}\n\nFixed decisions:\n{}",
+        NON_INTERACTIVE_INSTRUCTIONS,
+        decisions.join("\n")
+    )
+}
+
 /// Command for AI-assisted project initialization
 pub struct InitCommand {
     config: InitConfig,
@@ -79,23 +121,46 @@ impl Command for InitCommand {
         if let Some(no_confirm) = self.config.no_confirm {
             args.no_confirm = no_confirm;
         }
+        if self.config.non_interactive.unwrap_or(false) {
+            args.non_interactive = true;
+        }
+        if args.package_manager.is_none() {
+            args.package_manager = self.config.package_manager.clone();
+        }
+        if self.config.ci.unwrap_or(false) {
+            args.ci = true;
+        }
+        if args.license.is_none() {
+            args.license = self.config.license.clone();
+        }
         args
     }
 
     async fn execute(&self, args: InitArgs, agent: &CursorAgent) -> Result<()> {
         let mut prompt = self.prompt_template().to_string();
 
-        // Add language context if provided
-        if let Some(ref language) = args.language {
-            prompt = format!("{}\n\nTarget Language: {}", prompt, language);
+        if args.non_interactive {
+            if args.language.is_none() {
+                anyhow::bail!("--non-interactive needs --language");
+            }
+            prompt = format!("{}\n\n{}", prompt, fixed_decisions(&args));
+        } else {
+            // Add language context if provided
+            if let Some(ref language) = args.language {
+                prompt = format!("{}\n\nTarget Language: {}", prompt, language);
+            }
+
+            // Add project name context if provided
+            if let Some(ref name) = args.name {
+                prompt = format!("{}\n\nProject Name: {}", prompt, name);
+            }
         }
 
-        // Add project name context if provided
-        if let Some(ref name) = args.name {
-            prompt = format!("{}\n\nProject Name: {}", prompt, name);
+        // Add user message if provided
+        if let Some(always) = context::always_included_section() {
+            prompt = format!("{}\n\n{}", prompt, always);
         }
 
-        // Add user message if provided
         if let Some(ref message) = args.common.message {
             prompt = format!("{}\n\nUser Context: {}", prompt, message);
         }
@@ -103,7 +168,8 @@ impl Command for InitCommand {
         // Handle dry run
         if args.common.dry_run {
             println!(
-                "🔍 Dry run mode - would execute with prompt:\n---\n{}\n---",
+                "{}Dry run mode - would execute with prompt:\n---\n{}\n---",
+                icon(Icon::Search),
                 prompt
             );
             return Ok(());
@@ -113,3 +179,66 @@ impl Command for InitCommand {
         agent.execute(&prompt, args.no_confirm).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args::CommonArgs;
+
+    fn init_args() -> InitArgs {
+        InitArgs {
+            common: CommonArgs {
+                dry_run: true,
+                verbose: false,
+                message: None,
+            },
+            language: Some("rust".to_string()),
+            name: None,
+            non_interactive: true,
+            package_manager: None,
+            ci: false,
+            license: None,
+            no_readme: false,
+            no_confirm: false,
+        }
+    }
+
+    #[test]
+    fn test_fixed_decisions() {
+        let mut args = init_args();
+        args.name = Some("widget".to_string());
+        args.package_manager = Some("cargo".to_string());
+        args.ci = true;
+        args.license = Some("MIT".to_string());
+        args.no_readme = true;
+
+        let decisions = fixed_decisions(&args);
+        assert!(decisions.contains("Do NOT ask the user anything"));
+        assert!(decisions.contains("- Language: rust"));
+        assert!(decisions.contains("- Project name: widget"));
+        assert!(decisions.contains("- Package manager: cargo"));
+        assert!(decisions.contains("- GitHub Actions CI workflow: yes"));
+        assert!(decisions.contains("- License: MIT"));
+        assert!(decisions.contains("- README: no"));
+    }
+
+    #[test]
+    fn test_config_fills_unset_choices() {
+        let command = InitCommand::new(InitConfig {
+            non_interactive: Some(true),
+            package_manager: Some("uv".to_string()),
+            ci: Some(true),
+            license: Some("Apache-2.0".to_string()),
+            ..Default::default()
+        });
+        let mut args = init_args();
+        args.non_interactive = false;
+        args.license = Some("MIT".to_string());
+
+        let args = command.resolve_args(args);
+        assert!(args.non_interactive);
+        assert!(args.ci);
+        assert_eq!(args.package_manager.as_deref(), Some("uv"));
+        assert_eq!(args.license.as_deref(), Some("MIT"));
+    }
+}
@@ -1,7 +1,11 @@
 use crate::cli::args::MergeArgs;
 use crate::commands::Command;
 use crate::config::MergeConfig;
+use crate::context;
 use crate::cursor_agent::CursorAgent;
+use crate::git;
+use crate::pattern;
+use crate::terminal::{self, icon, Icon};
 use anyhow::Result;
 
 /// Merge prompt template
@@ -40,6 +44,20 @@ pub const MERGE_PROMPT: &str =
 
 Analyze the current repository state and provide comprehensive merge guidance for integrating '{}'.";
 
+/// Prompt for assessing predicted conflicts without performing the merge
+const CONFLICT_PREVIEW_PROMPT: &str =
+    "You are an expert software developer assessing a merge before it happens. Merging the branch '{}' \
+     into the current branch would conflict in the files listed below.
+
+For each file, compare both sides (e.g. `git diff HEAD...{}` and `git log --oneline HEAD...{}` for that path) and:
+- Explain what each side changed and why they collide
+- Rate how hard the conflict will be to resolve (trivial / moderate / hard)
+- Suggest how to resolve it
+
+Finish with an overall assessment and a recommended order for resolving the files.
+
+Do NOT start the merge, check out other branches, or modify any files: this is a read-only preview.";
+
 /// Merge command implementation
 pub struct MergeCommand {
     config: MergeConfig,
@@ -51,6 +69,99 @@ impl MergeCommand {
     }
 }
 
+impl MergeCommand {
+    /// Refuse to merge into a protected branch unless `--force` was given
+    fn ensure_unprotected(&self, args: &MergeArgs) -> Result<()> {
+        let Some(ref patterns) = self.config.protected_branches else {
+            return Ok(());
+        };
+        let Some(branch) = git::current_branch() else {
+            return Ok(());
+        };
+
+        if let Some(pattern) = protected_pattern(&branch, patterns) {
+            if !args.force {
+                anyhow::bail!(
+                    "Refusing to merge into protected branch {} (matches {}); use --force to merge anyway",
+                    branch,
+                    pattern
+                );
+            }
+            println!(
+                "{}Merging into protected branch {} (--force)",
+                icon(Icon::Warn),
+                branch
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Report the files a merge would conflict in, optionally asking the AI to
+    /// assess them, without starting the merge
+    async fn preview_conflicts(&self, args: &MergeArgs, agent: &CursorAgent) -> Result<()> {
+        if !git::rev_exists(&args.branch) {
+            anyhow::bail!("Unknown branch: {}", args.branch);
+        }
+
+        let conflicts = git::merge_conflicts(&args.branch)?;
+        if conflicts.is_empty() {
+            println!(
+                "{}Merging {} would complete without conflicts",
+                icon(Icon::Ok),
+                args.branch
+            );
+            return Ok(());
+        }
+
+        println!(
+            "{}Merging {} would conflict in {} file(s):",
+            icon(Icon::Warn),
+            args.branch,
+            conflicts.len()
+        );
+        for file in &conflicts {
+            println!("   {}", file);
+        }
+        println!();
+
+        let mut prompt = format!(
+            "{}\n\nConflicting files:\n{}",
+            CONFLICT_PREVIEW_PROMPT.replace("{}", &args.branch),
+            conflicts
+                .iter()
+                .map(|file| format!("- {}", file))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+
+        if let Some(always) = context::always_included_section() {
+            prompt = format!("{}\n\n{}", prompt, always);
+        }
+
+        if let Some(ref message) = args.common.message {
+            prompt = format!("{}\n\nUser context: {}", prompt, message);
+        }
+
+        if args.common.dry_run {
+            println!(
+                "{}Dry run mode - would execute with prompt:",
+                icon(Icon::Search)
+            );
+            println!("---");
+            println!("{}", prompt);
+            println!("---");
+            return Ok(());
+        }
+
+        if !args.no_confirm && !terminal::confirm("Ask the AI to assess these conflicts?")? {
+            return Ok(());
+        }
+
+        agent.execute(&prompt, false).await
+    }
+}
+
 impl Command for MergeCommand {
     type Args = MergeArgs;
     type Config = MergeConfig;
@@ -72,15 +183,32 @@ impl Command for MergeCommand {
     }
 
     async fn execute(&self, args: MergeArgs, agent: &CursorAgent) -> Result<()> {
+        if args.preview_conflicts {
+            return self.preview_conflicts(&args, agent).await;
+        }
+
+        self.ensure_unprotected(&args)?;
+
         // Use the template with branch substitution and custom message
         let mut prompt = self.prompt_template().replace("{}", &args.branch);
 
+        if let Some(tone) = context::tone_section(self.config.tone.as_deref()) {
+            prompt = format!("{}\n\n{}", prompt, tone);
+        }
+
+        if let Some(always) = context::always_included_section() {
+            prompt = format!("{}\n\n{}", prompt, always);
+        }
+
         if let Some(ref message) = args.common.message {
             prompt = format!("{}\n\nUser context: {}", prompt, message);
         }
 
         if args.common.dry_run {
-            println!("🔍 Dry run mode - would execute with prompt:");
+            println!(
+                "{}Dry run mode - would execute with prompt:",
+                icon(Icon::Search)
+            );
             println!("---");
             println!("{}", prompt);
             println!("---");
@@ -91,3 +219,28 @@ impl Command for MergeCommand {
         agent.execute(&prompt, args.no_confirm).await
     }
 }
+
+/// The first protected-branch pattern matching `branch`, if any
+fn protected_pattern<'a>(branch: &str, patterns: &'a [String]) -> Option<&'a str> {
+    patterns
+        .iter()
+        .map(String::as_str)
+        .find(|pattern| pattern::wildcard_match(pattern, branch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protected_pattern() {
+        let patterns = vec!["main".to_string(), "release/*".to_string()];
+        assert_eq!(protected_pattern("main", &patterns), Some("main"));
+        assert_eq!(
+            protected_pattern("release/1.2", &patterns),
+            Some("release/*")
+        );
+        assert_eq!(protected_pattern("feature/main", &patterns), None);
+        assert_eq!(protected_pattern("main", &[]), None);
+    }
+}
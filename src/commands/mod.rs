@@ -1,16 +1,32 @@
+pub mod amend_files;
 pub mod commit;
+pub mod completions;
 pub mod config;
 pub mod ignore;
 pub mod init;
 pub mod merge;
 pub mod pr;
+pub mod prepush;
+pub mod resolve;
+pub mod reword;
+pub mod ship;
+pub mod stats;
+pub mod undo;
 
+pub use amend_files::AmendFilesCommand;
 pub use commit::CommitCommand;
+pub use completions::CompletionsCommand;
 pub use config::ConfigCommand;
 pub use ignore::IgnoreCommand;
 pub use init::InitCommand;
 pub use merge::MergeCommand;
 pub use pr::PrCommand;
+pub use prepush::PrepushCommand;
+pub use resolve::ResolveCommand;
+pub use reword::RewordCommand;
+pub use ship::ShipCommand;
+pub use stats::StatsCommand;
+pub use undo::UndoCommand;
 
 use crate::cursor_agent::CursorAgent;
 use anyhow::Result;
@@ -1,8 +1,12 @@
 use crate::cli::args::PrArgs;
 use crate::commands::Command;
 use crate::config::PrConfig;
+use crate::context;
 use crate::cursor_agent::CursorAgent;
-use anyhow::Result;
+use crate::terminal::{icon, Icon};
+use crate::{git, language, pattern};
+use anyhow::{Context, Result};
+use std::path::Path;
 
 /// PR prompt template
 pub const PR_PROMPT: &str =
@@ -15,12 +19,8 @@ Analyze the git changes between the current branch and the target branch (typica
 2. **Analyze Impact**: Determine the scope and significance of the changes
 3. **Generate Description**: Create a well-structured PR description in Markdown format
 
-**Required Structure**:
-- **Summary**: Brief, clear overview of what this PR accomplishes
-- **Changes**: Bulleted list of key modifications, features, or fixes
-- **Why**: Explanation of the motivation, problem solved, or requirement fulfilled
-- **Testing**: Description of how changes were tested (unit tests, manual testing, etc.)
-- **Notes**: Any important considerations, breaking changes, or context for reviewers
+**Required Structure** (use exactly these sections, in this order):
+{sections}
 
 **Style Guidelines**:
 - Use clean Markdown formatting with proper headings
@@ -31,6 +31,73 @@ Analyze the git changes between the current branch and the target branch (typica
 
 Create a description that helps reviewers understand the context, changes, and impact of this pull request.";
 
+/// Sections a PR description can contain, with what each one covers
+const PR_SECTIONS: &[(&str, &str)] = &[
+    (
+        "Summary",
+        "Brief, clear overview of what this PR accomplishes",
+    ),
+    (
+        "Changes",
+        "Bulleted list of key modifications, features, or fixes",
+    ),
+    (
+        "Why",
+        "Explanation of the motivation, problem solved, or requirement fulfilled",
+    ),
+    (
+        "Testing",
+        "Description of how changes were tested (unit tests, manual testing, etc.)",
+    ),
+    (
+        "Notes",
+        "Any important considerations, breaking changes, or context for reviewers",
+    ),
+    (
+        "References",
+        "Related issues, tickets, and earlier pull requests",
+    ),
+];
+
+/// Sections used when `sections` is not configured
+pub const DEFAULT_PR_SECTIONS: &[&str] = &["Summary", "Changes", "Why", "Testing", "Notes"];
+
+/// Path patterns used to identify test files when none are configured
+pub const DEFAULT_TEST_PATTERNS: &[&str] = &[
+    "tests/",
+    "test/",
+    "__tests__/",
+    "spec/",
+    "*_test.rs",
+    "*_test.go",
+    "*_test.py",
+    "test_*.py",
+    "*.test.js",
+    "*.test.ts",
+    "*.spec.js",
+    "*.spec.ts",
+];
+
+/// Output format instructions for split title/body mode
+const SPLIT_OUTPUT_FORMAT: &str =
+    "Output format: print ONLY the pull request description, with nothing before or after it. \
+     The first line must be the PR title: plain text, no Markdown heading marker, under 72 characters. \
+     Follow it with one blank line, then the Markdown body using the required structure above. \
+     Do not modify any files or run commands that change the repository.";
+
+/// A PR description separated into its title and body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrDescription {
+    pub title: String,
+    pub body: String,
+}
+
+/// Commits of file history considered when suggesting reviewers
+const REVIEWER_HISTORY_COMMITS: usize = 500;
+
+/// Reviewers suggested when `reviewer_count` is not configured
+const DEFAULT_REVIEWER_COUNT: usize = 3;
+
 /// PR command implementation
 pub struct PrCommand {
     config: PrConfig,
@@ -40,6 +107,142 @@ impl PrCommand {
     pub fn new(config: PrConfig) -> Self {
         Self { config }
     }
+
+    /// Build a summary of the test files changed on this branch, or in
+    /// `patch_files` when describing a patch
+    fn tests_changed_context(
+        &self,
+        patch_files: Option<&[git::FileStatus]>,
+        verbose: bool,
+    ) -> Option<String> {
+        if !self.config.analyze_tests.unwrap_or(true) {
+            return None;
+        }
+
+        let (scope, changed) = match patch_files {
+            Some(files) => (
+                "in the patch".to_string(),
+                files.iter().map(|f| f.path.clone()).collect(),
+            ),
+            None => {
+                let base = git::default_base_branch()?;
+                match git::branch_changed_files(&base) {
+                    Ok(changed) => (format!("relative to {}", base), changed),
+                    Err(e) => {
+                        if verbose {
+                            println!("{}Skipping test analysis: {}", icon(Icon::Warn), e);
+                        }
+                        return None;
+                    }
+                }
+            }
+        };
+
+        let tests_changed: Vec<&String> = changed
+            .iter()
+            .filter(|path| !pattern::should_exclude(path))
+            .filter(|path| match self.config.test_patterns {
+                Some(ref patterns) => pattern::matches_any(patterns, path),
+                None => pattern::matches_any(DEFAULT_TEST_PATTERNS, path),
+            })
+            .collect();
+
+        if tests_changed.is_empty() {
+            return Some(format!(
+                "Tests changed ({}): none\n\
+                 No test files were modified by these changes. State this plainly in the Testing section instead of describing generic test coverage.",
+                scope
+            ));
+        }
+
+        let files = tests_changed
+            .iter()
+            .map(|path| format!("- {}", path))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Some(format!(
+            "Tests changed ({}):\n{}\n\n\
+             Review these test files and use them to describe concretely what is tested in the Testing section.",
+            scope, files
+        ))
+    }
+
+    /// Check the branch differs from its base before asking for a description.
+    /// Returns `false` when there is nothing to describe.
+    fn ensure_branch_changes(&self) -> Result<bool> {
+        let Some(base) = git::default_base_branch() else {
+            // Without a known base, let the agent work out what to compare against
+            return Ok(true);
+        };
+
+        if git::branch_changed_files(&base)?.is_empty() {
+            println!(
+                "{}Branch has no changes relative to {} - nothing to describe",
+                icon(Icon::Info),
+                base
+            );
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    /// Print the people who most often and most recently changed the files
+    /// in this PR (or in `patch_files`)
+    fn suggest_reviewers(&self, patch_files: Option<&[git::FileStatus]>) -> Result<()> {
+        let (rev, files) = match patch_files {
+            Some(files) => (
+                "HEAD".to_string(),
+                files.iter().map(|f| f.path.clone()).collect(),
+            ),
+            None => {
+                let base = git::default_base_branch()
+                    .context("Could not determine the base branch to compare against")?;
+                let files = git::branch_changed_files(&base)?;
+                (base, files)
+            }
+        };
+
+        let files: Vec<String> = files
+            .into_iter()
+            .filter(|path| !pattern::should_exclude(path))
+            .collect();
+        if files.is_empty() {
+            println!(
+                "{}No changed files to suggest reviewers for",
+                icon(Icon::Info)
+            );
+            return Ok(());
+        }
+
+        let authors = git::path_authors(&rev, &files, REVIEWER_HISTORY_COMMITS)?;
+        let mut exclude = self.config.reviewer_exclude.clone().unwrap_or_default();
+        exclude.extend(git::config_get_all("user.email"));
+        exclude.extend(git::config_get_all("user.name"));
+
+        let count = self.config.reviewer_count.unwrap_or(DEFAULT_REVIEWER_COUNT);
+        let reviewers = rank_reviewers(&authors, &exclude, count);
+        if reviewers.is_empty() {
+            println!(
+                "{}No other authors have changed these files",
+                icon(Icon::Info)
+            );
+            return Ok(());
+        }
+
+        println!("{}Suggested reviewers:", icon(Icon::Search));
+        for (author, commits) in reviewers {
+            println!(
+                "   {} ({} commit{} to these files)",
+                author,
+                commits,
+                if commits == 1 { "" } else { "s" }
+            );
+        }
+
+        Ok(())
+    }
 }
 
 impl Command for PrCommand {
@@ -53,6 +256,10 @@ impl Command for PrCommand {
 
     fn resolve_args(&self, mut args: PrArgs) -> PrArgs {
         // Apply config overrides to args
+        if self.config.split_title_body == Some(true) {
+            args.split = true;
+        }
+
         if let Some(no_confirm) = self.config.no_confirm {
             if !args.no_confirm {
                 // Only override if not explicitly set by CLI
@@ -63,22 +270,308 @@ impl Command for PrCommand {
     }
 
     async fn execute(&self, args: PrArgs, agent: &CursorAgent) -> Result<()> {
-        // Use the template with custom message if provided
-        let mut prompt = self.prompt_template().to_string();
+        // Fill the configured description sections into the template
+        let structure = match self.config.sections {
+            Some(ref sections) => required_structure(sections)?,
+            None => required_structure(DEFAULT_PR_SECTIONS)?,
+        };
+        let mut prompt = fill_sections(
+            self.prompt_template(),
+            &structure,
+            self.config.sections.is_some(),
+        );
+
+        if self.config.language_hints.unwrap_or(true) {
+            if let Some(hint) = language::language_hint_section() {
+                prompt = format!("{}\n\n{}", prompt, hint);
+            }
+        }
+
+        let patch = match args.patch {
+            Some(ref path) => Some(context::patch_section(path)?),
+            None => None,
+        };
+        let patch_files = patch.as_ref().map(|(_, files)| files.as_slice());
+
+        if args.reviewers {
+            return self.suggest_reviewers(patch_files);
+        }
+
+        if let Some(tests_changed) = self.tests_changed_context(patch_files, args.common.verbose) {
+            prompt = format!("{}\n\n{}", prompt, tests_changed);
+        }
+
+        if let Some((ref section, _)) = patch {
+            prompt = format!("{}\n\n{}", prompt, section);
+        }
+
+        if args.stat {
+            let base = git::default_base_branch()
+                .context("Could not determine the base branch to compare against")?;
+            if let Some(stat) = context::diffstat_section(&git::branch_diff_stat(&base)?) {
+                prompt = format!("{}\n\n{}", prompt, stat);
+            }
+        }
+
+        if !args.no_ticket {
+            if let Some(ticket) = context::branch_ticket(self.config.ticket_pattern.as_deref())? {
+                prompt = format!(
+                    "{}\n\nTicket: this branch belongs to ticket {}. End the description with a `Refs: {}` line.",
+                    prompt, ticket, ticket
+                );
+            }
+        }
+
+        if let Some(included) = context::included_files_section(&args.include)? {
+            prompt = format!("{}\n\n{}", prompt, included);
+        }
+
+        if let Some(tone) = context::tone_section(self.config.tone.as_deref()) {
+            prompt = format!("{}\n\n{}", prompt, tone);
+        }
+
+        if let Some(always) = context::always_included_section() {
+            prompt = format!("{}\n\n{}", prompt, always);
+        }
 
         if let Some(ref message) = args.common.message {
             prompt = format!("{}\n\nUser context: {}", prompt, message);
         }
 
+        let split = args.split || args.output.is_some();
+        if split {
+            prompt = format!("{}\n\n{}", prompt, SPLIT_OUTPUT_FORMAT);
+        }
+
         if args.common.dry_run {
-            println!("🔍 Dry run mode - would execute with prompt:");
+            println!(
+                "{}Dry run mode - would execute with prompt:",
+                icon(Icon::Search)
+            );
             println!("---");
             println!("{}", prompt);
             println!("---");
             return Ok(());
         }
 
-        // Use shared cursor-agent service
-        agent.execute(&prompt, args.no_confirm).await
+        if patch.is_none() && !self.ensure_branch_changes()? {
+            return Ok(());
+        }
+
+        if !split {
+            // Use shared cursor-agent service
+            return agent.execute(&prompt, args.no_confirm).await;
+        }
+
+        let output = agent.capture(&prompt).await?;
+        let description =
+            parse_title_body(&output).map_err(|e| agent.raw_response_error("pr", &output, e))?;
+
+        match args.output {
+            Some(ref dir) => {
+                write_description(dir, &description)?;
+                println!(
+                    "{}Wrote PR title and body to {}",
+                    icon(Icon::Saved),
+                    dir.display()
+                );
+            }
+            None => {
+                println!("{}\n\n{}", description.title, description.body);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Rank authors (newest commit first) by number of commits, breaking ties
+/// by recency, skipping anyone whose name or email is in `exclude`
+fn rank_reviewers(authors: &[String], exclude: &[String], count: usize) -> Vec<(String, usize)> {
+    let is_excluded = |author: &str| {
+        let author = author.to_lowercase();
+        exclude.iter().any(|excluded| {
+            let excluded = excluded.trim().to_lowercase();
+            !excluded.is_empty()
+                && (author == excluded
+                    || author.starts_with(&format!("{} <", excluded))
+                    || author.ends_with(&format!("<{}>", excluded)))
+        })
+    };
+
+    // (author, commits), in order of most recent commit
+    let mut ranked: Vec<(String, usize)> = Vec::new();
+    for author in authors.iter().filter(|author| !is_excluded(author)) {
+        match ranked.iter_mut().find(|(name, _)| name == author) {
+            Some((_, commits)) => *commits += 1,
+            None => ranked.push((author.clone(), 1)),
+        }
+    }
+
+    // Stable sort keeps the more recent author first on ties
+    ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+    ranked.truncate(count);
+    ranked
+}
+
+/// Bullet list of the requested description sections, in order. Section
+/// names are matched case-insensitively against [`PR_SECTIONS`].
+fn required_structure<S: AsRef<str>>(sections: &[S]) -> Result<String> {
+    if sections.is_empty() {
+        anyhow::bail!("commands.pr.sections must list at least one section");
+    }
+
+    sections
+        .iter()
+        .map(|section| {
+            let section = section.as_ref().trim();
+            PR_SECTIONS
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(section))
+                .map(|(name, description)| format!("- **{}**: {}", name, description))
+                .with_context(|| {
+                    format!(
+                        "Unknown PR section '{}' in commands.pr.sections (known: {})",
+                        section,
+                        PR_SECTIONS
+                            .iter()
+                            .map(|(name, _)| *name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(|lines| lines.join("\n"))
+}
+
+/// Put the section list into `template` at its `{sections}` placeholder. A
+/// custom prompt without the placeholder gets the list appended when sections
+/// are `configured`, and is otherwise left to define its own structure.
+fn fill_sections(template: &str, structure: &str, configured: bool) -> String {
+    if template.contains("{sections}") {
+        template.replace("{sections}", structure)
+    } else if configured {
+        format!(
+            "{}\n\nRequired structure (use exactly these sections, in this order):\n{}",
+            template, structure
+        )
+    } else {
+        template.to_string()
+    }
+}
+
+/// Split AI output into a title (first non-empty line) and body (the rest)
+fn parse_title_body(output: &str) -> Result<PrDescription> {
+    let mut lines = output.trim().lines();
+
+    let title = lines
+        .next()
+        .map(|line| {
+            let line = line.trim().trim_start_matches('#').trim();
+            line.strip_prefix("Title:")
+                .unwrap_or(line)
+                .trim()
+                .to_string()
+        })
+        .filter(|title| !title.is_empty())
+        .context("cursor-agent did not return a PR title")?;
+
+    let body = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+
+    Ok(PrDescription { title, body })
+}
+
+/// Write the title and body to `title` and `body` files in `dir`
+fn write_description(dir: &Path, description: &PrDescription) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create output directory: {}", dir.display()))?;
+
+    for (name, content) in [("title", &description.title), ("body", &description.body)] {
+        let path = dir.join(name);
+        std::fs::write(&path, format!("{}\n", content))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_structure() {
+        let default = required_structure(DEFAULT_PR_SECTIONS).unwrap();
+        assert_eq!(default.lines().count(), 5);
+        assert!(default.starts_with("- **Summary**: "));
+
+        let custom = required_structure(&["why", "Summary"]).unwrap();
+        let names: Vec<&str> = custom
+            .lines()
+            .map(|line| line.split("**").nth(1).unwrap())
+            .collect();
+        assert_eq!(names, vec!["Why", "Summary"]);
+
+        assert!(required_structure(&["Changelog"]).is_err());
+        assert!(required_structure::<&str>(&[]).is_err());
+    }
+
+    #[test]
+    fn test_fill_sections() {
+        let structure = "- **Why**: motivation";
+        assert!(!fill_sections(PR_PROMPT, structure, false).contains("{sections}"));
+        assert!(fill_sections(PR_PROMPT, structure, false).contains(structure));
+
+        let custom = "Describe this PR.";
+        assert_eq!(fill_sections(custom, structure, false), custom);
+        assert_eq!(
+            fill_sections(custom, structure, true),
+            "Describe this PR.\n\nRequired structure (use exactly these sections, in this order):\n\
+             - **Why**: motivation"
+        );
+    }
+
+    #[test]
+    fn test_parse_title_body() {
+        let output = "\nTitle: Add JWT authentication\n\n## Summary\nAdds auth.\n\n## Changes\n- middleware\n";
+        let description = parse_title_body(output).unwrap();
+        assert_eq!(description.title, "Add JWT authentication");
+        assert_eq!(
+            description.body,
+            "## Summary\nAdds auth.\n\n## Changes\n- middleware"
+        );
+
+        let description = parse_title_body("# Fix login redirect").unwrap();
+        assert_eq!(description.title, "Fix login redirect");
+        assert!(description.body.is_empty());
+
+        assert!(parse_title_body("  \n\n").is_err());
+    }
+
+    #[test]
+    fn test_rank_reviewers() {
+        let authors: Vec<String> = [
+            "Ada <ada@example.com>",
+            "Me <me@example.com>",
+            "Alan <alan@example.com>",
+            "Alan <alan@example.com>",
+            "Grace <grace@example.com>",
+            "Ada <ada@example.com>",
+            "Bot <bot@example.com>",
+        ]
+        .iter()
+        .map(|author| author.to_string())
+        .collect();
+        let exclude = vec!["me@example.com".to_string(), "Bot".to_string()];
+
+        assert_eq!(
+            rank_reviewers(&authors, &exclude, 2),
+            vec![
+                ("Ada <ada@example.com>".to_string(), 2),
+                ("Alan <alan@example.com>".to_string(), 2),
+            ]
+        );
+        assert_eq!(rank_reviewers(&authors, &exclude, 5).len(), 3);
     }
 }
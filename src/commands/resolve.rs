@@ -0,0 +1,126 @@
+use crate::cli::args::ResolveArgs;
+use crate::commands::Command;
+use crate::config::ResolveConfig;
+use crate::context;
+use crate::cursor_agent::CursorAgent;
+use crate::git;
+use crate::terminal::{icon, Icon};
+use anyhow::{Context, Result};
+
+/// Resolve prompt template
+pub const RESOLVE_PROMPT: &str =
+    "You are an expert software developer helping resolve merge conflicts that are already in progress in this Git repository.
+
+**Your Task**:
+1. **Understand Both Sides**: For each conflicted file below, read the conflict markers (`<<<<<<<`, `=======`, `>>>>>>>`) and use `git log --merge` and `git diff` to understand what each side intended
+2. **Propose a Resolution**: Explain, per file, how you will combine the two sides and why
+3. **Apply It**: Edit the file to the resolved content with every conflict marker removed, then stage it with `git add <file>`
+
+**Rules**:
+- Preserve the intent of both sides; do not simply pick one side unless the other change is obsolete
+- Never run `git merge --abort`, `git rebase --abort`, `git cherry-pick --abort`, `git reset`, or `git checkout` on conflicted files
+- Do not commit or continue the merge/rebase; leave that to the user
+- If a conflict cannot be resolved confidently, leave the markers in place and explain what decision the user needs to make";
+
+/// Resolve command implementation
+pub struct ResolveCommand {
+    config: ResolveConfig,
+}
+
+impl ResolveCommand {
+    pub fn new(config: ResolveConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Command for ResolveCommand {
+    type Args = ResolveArgs;
+    type Config = ResolveConfig;
+
+    fn prompt_template(&self) -> &str {
+        // Use custom prompt from config, or default
+        self.config.prompt.as_deref().unwrap_or(RESOLVE_PROMPT)
+    }
+
+    fn resolve_args(&self, mut args: ResolveArgs) -> ResolveArgs {
+        // Apply config overrides to args
+        if let Some(no_confirm) = self.config.no_confirm {
+            if !args.no_confirm {
+                // Only override if not explicitly set by CLI
+                args.no_confirm = no_confirm;
+            }
+        }
+        args
+    }
+
+    async fn execute(&self, args: ResolveArgs, agent: &CursorAgent) -> Result<()> {
+        let status = git::repository_status().context("Failed to read repository status")?;
+        if !status.has_conflicts() {
+            println!("{}No merge conflicts to resolve", icon(Icon::Info));
+            return Ok(());
+        }
+
+        let conflicted: Vec<&str> = status
+            .conflicted()
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
+
+        println!(
+            "{}Found {} conflicted file(s):",
+            icon(Icon::Files),
+            conflicted.len()
+        );
+        for path in &conflicted {
+            println!("   {}", path);
+        }
+        println!();
+
+        let mut prompt = format!(
+            "{}\n\n{}",
+            self.prompt_template(),
+            context::conflicted_files_section(&git::repo_root()?, &conflicted)
+        );
+
+        if let Some(always) = context::always_included_section() {
+            prompt = format!("{}\n\n{}", prompt, always);
+        }
+
+        if let Some(ref message) = args.common.message {
+            prompt = format!("{}\n\nUser context: {}", prompt, message);
+        }
+
+        if args.common.dry_run {
+            println!(
+                "{}Dry run mode - would execute with prompt:",
+                icon(Icon::Search)
+            );
+            println!("---");
+            println!("{}", prompt);
+            println!("---");
+            return Ok(());
+        }
+
+        // Use shared cursor-agent service
+        agent.execute(&prompt, args.no_confirm).await?;
+
+        let remaining = git::repository_status()
+            .context("Failed to read repository status")?
+            .conflicted()
+            .len();
+        if remaining > 0 {
+            println!(
+                "{}{} file(s) still have unresolved conflicts",
+                icon(Icon::Warn),
+                remaining
+            );
+        } else {
+            println!(
+                "{}All conflicts resolved and staged - review the result, then commit or continue",
+                icon(Icon::Ok)
+            );
+        }
+
+        Ok(())
+    }
+}
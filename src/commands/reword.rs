@@ -0,0 +1,232 @@
+use crate::cli::args::RewordArgs;
+use crate::commands::commit::{allowed_types_policy, body_policy, clean_message};
+use crate::commands::Command;
+use crate::config::{CommitConfig, RewordConfig};
+use crate::context;
+use crate::cursor_agent::CursorAgent;
+use crate::git;
+use crate::spellcheck;
+use crate::terminal::{self, icon, Icon};
+use anyhow::{Context, Result};
+
+/// Reword prompt template
+pub const REWORD_PROMPT: &str =
+"You are operating in a command line interface, rewriting the message of an existing commit in a Git repository.
+
+Your task:
+
+1. **Analyze the commit.**
+   - Run `git show {}` to see its current message and the changes it introduced.
+   - Only consider the changes in that commit; ignore the working tree and other commits.
+
+2. **Write an improved commit message following the Conventional Commits standard.**
+   - Use the format: `<type>(<optional scope>): <short description>`
+   - Subject line under **72 characters**, written in **present tense**.
+   - Keep the body short, following the message body guidance below.
+   - Keep any trailers from the current message (e.g. `Co-authored-by:`, `Refs:`) unchanged at the end.
+
+3. **Output only the new commit message.**
+   - Print the message and nothing else: no explanations, no code fences.
+   - Do NOT run `git commit`, `git rebase`, or any command that changes the repository.
+";
+
+/// Reword command implementation
+pub struct RewordCommand {
+    config: RewordConfig,
+    commit: CommitConfig,
+}
+
+impl RewordCommand {
+    pub fn new(config: RewordConfig, commit: CommitConfig) -> Self {
+        Self { config, commit }
+    }
+
+    /// Resolve the commit to reword and check it can be rewritten safely
+    fn target_commit(&self, args: &RewordArgs) -> Result<String> {
+        let sha = git::run(&["rev-parse", "--verify", &format!("{}^{{commit}}", args.rev)])
+            .with_context(|| format!("Unknown commit: {}", args.rev))?;
+
+        if !git::is_ancestor(&sha, "HEAD") {
+            anyhow::bail!("{} is not on the current branch", args.rev);
+        }
+
+        if git::commit_parents(&sha)?.len() > 1 {
+            anyhow::bail!(
+                "{} is a merge commit; rewording merges is not supported",
+                args.rev
+            );
+        }
+
+        let merges = git::run(&["rev-list", "--merges", &format!("{}..HEAD", sha)])?;
+        if !merges.is_empty() {
+            anyhow::bail!(
+                "There are merge commits after {}; rewording would flatten them",
+                args.rev
+            );
+        }
+
+        let pushed_to = git::remote_branches_containing(&sha)?;
+        if !pushed_to.is_empty() && !args.force {
+            anyhow::bail!(
+                "{} has already been pushed ({}); rewording rewrites history. Use --force to reword anyway",
+                args.rev,
+                pushed_to.join(", ")
+            );
+        }
+
+        Ok(sha)
+    }
+}
+
+impl Command for RewordCommand {
+    type Args = RewordArgs;
+    type Config = RewordConfig;
+
+    fn prompt_template(&self) -> &str {
+        // Use custom prompt from config, or default
+        self.config.prompt.as_deref().unwrap_or(REWORD_PROMPT)
+    }
+
+    fn resolve_args(&self, mut args: RewordArgs) -> RewordArgs {
+        // Apply config overrides to args
+        if let Some(no_confirm) = self.config.no_confirm {
+            if !args.no_confirm {
+                // Only override if not explicitly set by CLI
+                args.no_confirm = no_confirm;
+            }
+        }
+        args
+    }
+
+    async fn execute(&self, args: RewordArgs, agent: &CursorAgent) -> Result<()> {
+        let sha = self.target_commit(&args)?;
+
+        // Use the template with commit substitution and custom message
+        let mut prompt = self.prompt_template().replace("{}", &sha);
+        prompt = format!(
+            "{}\n\n{}\n\n{}",
+            prompt,
+            allowed_types_policy(&self.commit),
+            body_policy(&self.commit)
+        );
+
+        if let Some(tone) = context::tone_section(self.commit.tone.as_deref()) {
+            prompt = format!("{}\n\n{}", prompt, tone);
+        }
+
+        if let Some(always) = context::always_included_section() {
+            prompt = format!("{}\n\n{}", prompt, always);
+        }
+
+        if let Some(ref message) = args.common.message {
+            prompt = format!("{}\n\nUser context: {}", prompt, message);
+        }
+
+        if args.common.dry_run {
+            println!(
+                "{}Dry run mode - would execute with prompt:",
+                icon(Icon::Search)
+            );
+            println!("---");
+            println!("{}", prompt);
+            println!("---");
+            return Ok(());
+        }
+
+        let status = git::repository_status().context("Failed to read repository status")?;
+        if status.has_staged() || status.has_unstaged() {
+            anyhow::bail!("Commit or stash your changes before rewording history");
+        }
+
+        let output = agent.capture(&prompt).await?;
+        let message =
+            clean_message(&output).map_err(|e| agent.raw_response_error("reword", &output, e))?;
+        let current = git::commit_message(&sha)?;
+        if self.commit.spellcheck.unwrap_or(false) {
+            spellcheck::warn(&message);
+        }
+
+        println!("{}New message:", icon(Icon::Working));
+        println!("{}", terminal::render_message(&message));
+        println!();
+        println!("{}Changes from the current message:", icon(Icon::Info));
+        println!("{}", terminal::render_message_diff(&current, &message));
+        println!();
+
+        if !args.no_confirm && !terminal::confirm("Reword this commit?")? {
+            println!("{}Reword cancelled", icon(Icon::Error));
+            return Ok(());
+        }
+
+        reword_commit(&sha, &message)?;
+        println!("{}Reworded {}", icon(Icon::Ok), &sha[..7.min(sha.len())]);
+
+        Ok(())
+    }
+}
+
+/// Environment variables naming the files the scripted editors copy into place
+const TODO_ENV: &str = "GITAI_REWORD_TODO";
+const MESSAGE_ENV: &str = "GITAI_REWORD_MESSAGE";
+
+/// Replace a commit's message by replaying the branch from its parent with a
+/// scripted interactive rebase. The todo list and new message are written to
+/// files that git's sequence and message editors simply copy into place.
+fn reword_commit(sha: &str, message: &str) -> Result<()> {
+    let parent = git::commit_parents(sha)?.into_iter().next();
+    let range = match parent {
+        Some(ref parent) => format!("{}..HEAD", parent),
+        None => "HEAD".to_string(),
+    };
+    let commits = git::run(&["rev-list", "--reverse", &range])?;
+
+    let todo = commits
+        .lines()
+        .map(|commit| {
+            let action = if commit == sha { "reword" } else { "pick" };
+            format!("{} {}\n", action, commit)
+        })
+        .collect::<String>();
+
+    // Absolute, because git runs the editors from the top of the worktree
+    let todo_path = git::absolute_git_path("git-ai/reword-todo")?;
+    let message_path = git::absolute_git_path("git-ai/reword-message")?;
+    if let Some(dir) = todo_path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+    }
+    std::fs::write(&todo_path, todo).context("Failed to write rebase todo list")?;
+    std::fs::write(&message_path, format!("{}\n", message))
+        .context("Failed to write new commit message")?;
+
+    // The editors read the paths from the environment, so no quoting is needed
+    let sequence_editor = format!("cp \"${}\"", TODO_ENV);
+    let editor = format!("cp \"${}\"", MESSAGE_ENV);
+    let todo_path = todo_path.to_string_lossy();
+    let message_path = message_path.to_string_lossy();
+    let mut rebase = vec!["rebase", "--interactive", "--no-autosquash"];
+    match parent {
+        Some(ref parent) => rebase.push(parent),
+        None => rebase.push("--root"),
+    }
+
+    let result = git::run_with_env(
+        &rebase,
+        &[
+            ("GIT_SEQUENCE_EDITOR", &sequence_editor),
+            ("GIT_EDITOR", &editor),
+            (TODO_ENV, &todo_path),
+            (MESSAGE_ENV, &message_path),
+        ],
+    );
+
+    let _ = std::fs::remove_file(todo_path.as_ref());
+    let _ = std::fs::remove_file(message_path.as_ref());
+
+    if let Err(e) = result {
+        let _ = git::run(&["rebase", "--abort"]);
+        return Err(e.context("Failed to reword commit; the branch was left unchanged"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::cli::args::{CommitArgs, PrArgs, ShipArgs};
+use crate::commands::{Command, CommitCommand, PrCommand};
+use crate::config::{CommitConfig, PrConfig, ShipConfig};
+use crate::cursor_agent::CursorAgent;
+use crate::git;
+use crate::terminal::{self, icon, Icon};
+use anyhow::{Context, Result};
+
+/// Remote pushed to when none is configured
+pub const DEFAULT_REMOTE: &str = "origin";
+
+/// Ship command implementation: commit, push, and describe the pull request
+/// by running the commit and PR commands in sequence (no prompt of its own)
+pub struct ShipCommand {
+    config: ShipConfig,
+    commit: CommitConfig,
+    pr: PrConfig,
+}
+
+impl ShipCommand {
+    pub fn new(config: ShipConfig, commit: CommitConfig, pr: PrConfig) -> Self {
+        Self { config, commit, pr }
+    }
+
+    /// Commit pending changes with the commit command. Returns `false` if
+    /// there were changes but no commit was made (cancelled or only drafted).
+    async fn commit_step(&self, args: &ShipArgs, agent: &CursorAgent) -> Result<bool> {
+        let status = git::repository_status().context("Failed to read repository status")?;
+        if status.is_clean() {
+            println!(
+                "{}No changes to commit - shipping the existing commits",
+                icon(Icon::Info)
+            );
+            return Ok(true);
+        }
+
+        let cmd = CommitCommand::new(self.commit.clone());
+        let commit_args = cmd.resolve_args(CommitArgs {
+            common: args.common.clone(),
+            include: Vec::new(),
+            stat: false,
+            oneline: false,
+            no_verify: false,
+            no_ticket: false,
+            signoff: false,
+            only: Vec::new(),
+            template_from_commit: None,
+            json: false,
+            message_only: false,
+            allow_empty: false,
+            no_confirm: args.no_confirm,
+        });
+
+        let head_before = git::head_commit();
+        cmd.execute(commit_args, agent).await?;
+        Ok(args.common.dry_run || git::head_commit() != head_before)
+    }
+
+    /// Push `branch` and set its upstream. Returns `false` if the user declined.
+    fn push_step(&self, args: &ShipArgs, branch: &str) -> Result<bool> {
+        let remote = self.config.remote.as_deref().unwrap_or(DEFAULT_REMOTE);
+
+        if args.common.dry_run {
+            println!(
+                "{}Dry run mode - would push {} to {}",
+                icon(Icon::Search),
+                branch,
+                remote
+            );
+            return Ok(true);
+        }
+
+        if !args.no_confirm && !terminal::confirm(&format!("Push {} to {}?", branch, remote))? {
+            println!("{}Ship stopped before pushing", icon(Icon::Error));
+            return Ok(false);
+        }
+
+        git::push(remote, branch)?;
+        println!("{}Pushed {} to {}", icon(Icon::Ok), branch, remote);
+        Ok(true)
+    }
+
+    /// Generate the pull request description with the PR command
+    async fn pr_step(&self, args: &ShipArgs, agent: &CursorAgent) -> Result<()> {
+        if !args.common.dry_run
+            && !args.no_confirm
+            && !terminal::confirm("Generate the pull request description?")?
+        {
+            println!("{}Skipped the pull request description", icon(Icon::Info));
+            return Ok(());
+        }
+
+        let cmd = PrCommand::new(self.pr.clone());
+        let pr_args = cmd.resolve_args(PrArgs {
+            common: args.common.clone(),
+            include: Vec::new(),
+            patch: None,
+            stat: false,
+            no_ticket: false,
+            split: false,
+            output: None,
+            reviewers: false,
+            no_confirm: args.no_confirm,
+        });
+        cmd.execute(pr_args, agent).await
+    }
+}
+
+impl Command for ShipCommand {
+    type Args = ShipArgs;
+    type Config = ShipConfig;
+
+    fn prompt_template(&self) -> &str {
+        "" // Ship runs the commit and PR prompts
+    }
+
+    fn resolve_args(&self, mut args: ShipArgs) -> ShipArgs {
+        // Apply config overrides to args
+        if let Some(no_confirm) = self.config.no_confirm {
+            if !args.no_confirm {
+                // Only override if not explicitly set by CLI
+                args.no_confirm = no_confirm;
+            }
+        }
+        args
+    }
+
+    async fn execute(&self, args: ShipArgs, agent: &CursorAgent) -> Result<()> {
+        let branch = git::current_branch().context("Ship needs a branch; HEAD is detached")?;
+        let base = git::default_base_branch();
+        let base = base
+            .as_deref()
+            .map(|base| base.strip_prefix("origin/").unwrap_or(base));
+        if base == Some(branch.as_str()) {
+            anyhow::bail!(
+                "{} is the base branch; switch to a feature branch before shipping",
+                branch
+            );
+        }
+
+        println!("{}Step 1/3: commit", icon(Icon::Working));
+        if !self.commit_step(&args, agent).await? {
+            println!(
+                "{}No commit was created - ship stopped before pushing",
+                icon(Icon::Error)
+            );
+            return Ok(());
+        }
+
+        println!("{}Step 2/3: push", icon(Icon::Working));
+        if !self.push_step(&args, &branch)? {
+            return Ok(());
+        }
+
+        println!("{}Step 3/3: pull request description", icon(Icon::Working));
+        self.pr_step(&args, agent).await
+    }
+}
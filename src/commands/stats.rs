@@ -0,0 +1,125 @@
+use crate::commands::commit::GENERATED_BY_TRAILER;
+use crate::commands::Command;
+use crate::cursor_agent::CursorAgent;
+use crate::git;
+use crate::history::{Action, History};
+use crate::terminal::{icon, Icon};
+use anyhow::{Context, Result};
+
+/// Stats command implementation (no prompt needed)
+pub struct StatsCommand;
+
+impl StatsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Command for StatsCommand {
+    type Args = ();
+    type Config = (); // Stats command doesn't need config
+
+    fn prompt_template(&self) -> &str {
+        "" // No prompt for stats command
+    }
+
+    fn resolve_args(&self, args: ()) {
+        // No overrides for stats command
+        args
+    }
+
+    async fn execute(&self, _args: (), _agent: &CursorAgent) -> Result<()> {
+        if !git::has_commits() {
+            println!("{}No commits yet - nothing to report", icon(Icon::Info));
+            return Ok(());
+        }
+
+        let total = git::commit_count(&[]).context("Failed to count commits")?;
+        let generated = git::commit_count(&[
+            "--fixed-strings",
+            &format!("--grep={}", GENERATED_BY_TRAILER),
+        ])
+        .context("Failed to count generated commits")?;
+
+        println!("{}git-ai usage in this repository:", icon(Icon::Info));
+        println!(
+            "   Commits with `{}`: {} of {} ({:.1}%)",
+            GENERATED_BY_TRAILER,
+            generated,
+            total,
+            percentage(generated, total)
+        );
+
+        let history = History::load()?;
+        if history.actions.is_empty() {
+            println!("   No git-ai actions recorded in this clone");
+        } else {
+            println!("   Actions recorded in this clone:");
+            for (command, count) in count_by_command(&history.actions) {
+                println!("     git ai {}: {}", command, count);
+            }
+        }
+
+        if generated == 0 {
+            println!();
+            println!(
+                "{}Set `commands.commit.generated_by_trailer: true` to mark generated commits",
+                icon(Icon::Hint)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Share of `part` in `total` as a percentage, 0 when `total` is 0
+fn percentage(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+/// Number of recorded actions per command, most frequent first
+fn count_by_command(actions: &[Action]) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for action in actions {
+        match counts
+            .iter_mut()
+            .find(|(command, _)| *command == action.command)
+        {
+            Some((_, count)) => *count += 1,
+            None => counts.push((action.command.clone(), 1)),
+        }
+    }
+
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(command: &str) -> Action {
+        Action {
+            command: command.to_string(),
+            head_before: None,
+            head_after: "abc123".to_string(),
+            timestamp: 0,
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_count_by_command() {
+        let actions = [action("commit"), action("pr"), action("commit")];
+        assert_eq!(
+            count_by_command(&actions),
+            vec![("commit".to_string(), 2), ("pr".to_string(), 1)]
+        );
+        assert_eq!(percentage(1, 4), 25.0);
+        assert_eq!(percentage(0, 0), 0.0);
+    }
+}
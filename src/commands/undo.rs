@@ -0,0 +1,165 @@
+use crate::cli::args::UndoArgs;
+use crate::commands::Command;
+use crate::cursor_agent::CursorAgent;
+use crate::git;
+use crate::history::History;
+use crate::terminal::{self, icon, Icon};
+use anyhow::{Context, Result};
+
+/// Undo command implementation (no prompt needed)
+pub struct UndoCommand;
+
+impl UndoCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Command for UndoCommand {
+    type Args = UndoArgs;
+    type Config = (); // Undo command doesn't need config
+
+    fn prompt_template(&self) -> &str {
+        "" // No prompt for undo command
+    }
+
+    fn resolve_args(&self, args: UndoArgs) -> UndoArgs {
+        // No overrides for undo command
+        args
+    }
+
+    async fn execute(&self, args: UndoArgs, _agent: &CursorAgent) -> Result<()> {
+        let mut history = History::load()?;
+
+        let Some(action) = history.actions.last().cloned() else {
+            println!(
+                "{}No git-ai actions recorded - nothing to undo",
+                icon(Icon::Info)
+            );
+            return Ok(());
+        };
+
+        let head = git::head_commit();
+        if head.as_deref() != Some(action.head_after.as_str()) {
+            anyhow::bail!(
+                "HEAD has moved since `git ai {}` ran (expected {}); refusing to undo",
+                action.command,
+                short_sha(&action.head_after)
+            );
+        }
+
+        let moved_head = action.head_before.as_deref() != Some(action.head_after.as_str());
+
+        // Undoing a pushed commit would make the next push diverge from the remote
+        let pushed_to = if moved_head {
+            git::remote_branches_containing(&action.head_after)?
+        } else {
+            Vec::new()
+        };
+        if !pushed_to.is_empty() {
+            anyhow::bail!(
+                "`git ai {}` has already been pushed ({}); refusing to undo published commits. \
+                 Use `git revert` instead",
+                action.command,
+                pushed_to.join(", ")
+            );
+        }
+
+        let root = git::repo_root()?;
+        for file in &action.files {
+            let current = std::fs::read_to_string(root.join(&file.path)).ok();
+            if current != file.after {
+                anyhow::bail!(
+                    "{} has changed since `git ai {}` ran; refusing to undo",
+                    file.path,
+                    action.command
+                );
+            }
+        }
+
+        println!(
+            "{}Last git-ai action: `git ai {}`",
+            icon(Icon::Undo),
+            action.command
+        );
+        if moved_head {
+            match action.head_before {
+                // Commits added on top of the old HEAD
+                Some(ref before) if git::is_ancestor(before, &action.head_after) => {
+                    let range = format!("{}..{}", before, action.head_after);
+                    println!("   Commits to undo (changes stay staged):");
+                    for line in git::run(&["log", "--oneline", &range])?.lines() {
+                        println!("   {}", line);
+                    }
+                }
+                // History was rewritten (e.g. by reword), so only the ends are meaningful
+                Some(ref before) => println!(
+                    "   HEAD will move back from {} to {} (changes stay staged)",
+                    short_sha(&action.head_after),
+                    short_sha(before)
+                ),
+                None => {
+                    println!("   Commits to undo (changes stay staged):");
+                    for line in git::run(&["log", "--oneline", &action.head_after])?.lines() {
+                        println!("   {}", line);
+                    }
+                }
+            }
+        }
+        if !action.files.is_empty() {
+            println!("   Files to restore:");
+            for file in &action.files {
+                println!("   {}", file.path);
+            }
+        }
+        println!();
+
+        if args.dry_run {
+            println!("{}Dry run mode - no changes made", icon(Icon::Search));
+            return Ok(());
+        }
+
+        if !args.no_confirm && !terminal::confirm("Undo this action?")? {
+            println!("{}Undo cancelled", icon(Icon::Error));
+            return Ok(());
+        }
+
+        if moved_head {
+            match action.head_before {
+                Some(ref before) => {
+                    git::run(&["reset", "--soft", before])?;
+                }
+                None => {
+                    // The action created the first commit; return the branch to its unborn state
+                    git::run(&["update-ref", "-d", "HEAD"])?;
+                }
+            }
+        }
+
+        for file in &action.files {
+            let path = root.join(&file.path);
+            match file.before {
+                Some(ref content) => std::fs::write(&path, content)
+                    .with_context(|| format!("Failed to restore {}", path.display()))?,
+                None => std::fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?,
+            }
+
+            // A restored file the action also committed must not stay staged
+            if moved_head && action.head_before.is_some() {
+                git::run(&["reset", "-q", "--", &format!(":(top,literal){}", file.path)])?;
+            }
+        }
+
+        history.actions.pop();
+        history.save()?;
+
+        println!("{}Undid `git ai {}`", icon(Icon::Ok), action.command);
+        Ok(())
+    }
+}
+
+/// Abbreviate a commit sha for display
+fn short_sha(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
@@ -1,26 +1,121 @@
+use crate::terminal::{icon, Icon};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Deserialize, Serialize, Default)]
+/// Current config file format version
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Name of the repository (and subdirectory) config file
+pub const REPO_CONFIG_FILE: &str = ".git-ai.yaml";
+
+/// Upgrade steps for config files, indexed by the version they migrate from
+const MIGRATIONS: &[fn(&mut Mapping)] = &[migrate_v0_to_v1];
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
+    /// Config file format version, used by `git ai config --migrate`
+    #[serde(default = "default_config_version")]
+    pub version: u32,
+
     #[serde(default)]
     pub behavior: BehaviorConfig,
 
+    #[serde(default)]
+    pub agent: AgentConfig,
+
+    #[serde(default)]
+    pub git: GitConfig,
+
+    #[serde(default)]
+    pub context: ContextConfig,
+
     #[serde(default)]
     pub commands: CommandConfigs,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: default_config_version(),
+            behavior: BehaviorConfig::default(),
+            agent: AgentConfig::default(),
+            git: GitConfig::default(),
+            context: ContextConfig::default(),
+            commands: CommandConfigs::default(),
+        }
+    }
+}
+
+fn default_config_version() -> u32 {
+    CONFIG_VERSION
+}
+
+/// How cursor-agent is invoked
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct AgentConfig {
+    /// Path to the cursor-agent executable (default: `cursor-agent` from PATH)
+    pub binary: Option<PathBuf>,
+    /// Keep responses that fail to parse in `.git/git-ai/debug/` (default: false)
+    pub save_raw_responses: Option<bool>,
+    /// Subcommand that takes the prompt (default: `prompt`; empty passes the prompt directly)
+    pub subcommand: Option<String>,
+    /// Flag asking the agent to print its response instead of acting (default: `--print`)
+    pub print_flag: Option<String>,
+}
+
+/// How git is invoked
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct GitConfig {
+    /// Path to the git executable (default: `git` from PATH)
+    pub binary: Option<PathBuf>,
+}
+
+/// What git-ai reads from the repository to build prompts
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ContextConfig {
+    /// Generated or vendored paths left out of every scan (default: vendor/,
+    /// node_modules/, target/, dist/)
+    pub exclude: Option<Vec<String>>,
+    /// Diff lines longer than this many characters, such as minified bundles,
+    /// are collapsed to a one-line note (default: 2000)
+    pub max_line_length: Option<usize>,
+    /// Files, relative to the repository root, added to every AI command's
+    /// prompt, such as a conventions document (text files up to 100 KB)
+    pub always_include: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct BehaviorConfig {
     #[serde(default = "default_verbose")]
     pub verbose: bool,
+
+    /// Decorate status output with emoji (plain ASCII prefixes when false)
+    #[serde(default = "default_emoji")]
+    pub emoji: bool,
+
+    /// Command line run when git-ai is invoked without a subcommand, e.g. `commit`
+    #[serde(default)]
+    pub default_command: Option<String>,
+
+    /// Highlight previewed commit messages with color
+    #[serde(default = "default_color")]
+    pub color: bool,
+
+    /// Column previewed commit messages are wrapped at (default: terminal width)
+    #[serde(default)]
+    pub preview_width: Option<usize>,
 }
 
 impl Default for BehaviorConfig {
     fn default() -> Self {
         Self {
             verbose: default_verbose(),
+            emoji: default_emoji(),
+            default_command: None,
+            color: default_color(),
+            preview_width: None,
         }
     }
 }
@@ -29,6 +124,14 @@ fn default_verbose() -> bool {
     false
 }
 
+fn default_emoji() -> bool {
+    true
+}
+
+fn default_color() -> bool {
+    true
+}
+
 /// Configuration for individual commands
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct CommandConfigs {
@@ -46,6 +149,21 @@ pub struct CommandConfigs {
 
     #[serde(default)]
     pub ignore: IgnoreConfig,
+
+    #[serde(default)]
+    pub amend_files: AmendFilesConfig,
+
+    #[serde(default)]
+    pub prepush: PrepushConfig,
+
+    #[serde(default)]
+    pub reword: RewordConfig,
+
+    #[serde(default)]
+    pub resolve: ResolveConfig,
+
+    #[serde(default)]
+    pub ship: ShipConfig,
 }
 
 /// Configuration for commit command
@@ -53,6 +171,73 @@ pub struct CommandConfigs {
 pub struct CommitConfig {
     pub prompt: Option<String>,
     pub no_confirm: Option<bool>,
+    /// Where to read pairing co-authors from (default: auto)
+    pub co_authors: Option<CoAuthorSource>,
+    /// Number of recent commit messages to show verbatim as a style example
+    pub style_anchor_commits: Option<usize>,
+    /// Whether changes become one commit or several logical ones (default: atomic)
+    pub grouping: Option<CommitGrouping>,
+    /// Conventional commit types the AI may use (default: the standard set)
+    pub allowed_types: Option<Vec<String>>,
+    /// Regex extracting a ticket id from the branch name, added as a `Refs:` trailer
+    pub ticket_pattern: Option<String>,
+    /// Mark generated commits with a `Generated-by: git-ai` trailer (default: false)
+    pub generated_by_trailer: Option<bool>,
+    /// Record the agent version and a prompt hash as trailers (default: false)
+    pub provenance_trailers: Option<bool>,
+    /// Changes of at most this many lines get a subject-only message (same as `--oneline`)
+    pub oneline_threshold: Option<usize>,
+    /// Add scope conventions for the repository's primary language (default: true)
+    pub language_hints: Option<bool>,
+    /// Maximum bullet points in the message body (default: 2)
+    pub body_bullets: Option<usize>,
+    /// Write a message body at all, or only a subject line (default: true)
+    pub include_body: Option<bool>,
+    /// Tone of generated messages: terse, formal, friendly, or a free-form hint
+    pub tone: Option<String>,
+    /// Markers flagged when added by the change (default: TODO, FIXME, XXX; empty disables)
+    pub pending_markers: Option<Vec<String>>,
+    /// Extra trailers for every commit; `{user.name}`, `{user.email}`, and
+    /// `{branch}` are filled in
+    pub footers: Option<Vec<String>>,
+    /// Ask before committing files larger than this many MB (default: 5; 0 disables)
+    pub large_file_mb: Option<u64>,
+    /// Ask before committing binary files not stored with Git LFS (default: true)
+    pub warn_binary_files: Option<bool>,
+    /// Let the AI run `git commit` (default: true); when false, only draft the message
+    pub auto_commit: Option<bool>,
+    /// Include the full staged content of changed text files up to this many KB
+    /// alongside the diff (default: 0, disabled)
+    pub staged_content_kb: Option<u64>,
+    /// Warn about common misspellings in generated messages before they are
+    /// committed (default: false)
+    pub spellcheck: Option<bool>,
+}
+
+/// How staged changes are grouped into commits
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitGrouping {
+    /// Exactly one commit for everything staged
+    Single,
+    /// One commit per logical change
+    #[default]
+    Atomic,
+}
+
+/// Source of `Co-authored-by:` trailers for generated commits
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum CoAuthorSource {
+    /// Check `git config git-ai.co-authors`, then `.git/PAIRING`
+    #[default]
+    Auto,
+    /// Only `git config git-ai.co-authors`
+    GitConfig,
+    /// Only the `.git/PAIRING` file written by pairing tools
+    PairingFile,
+    /// Never add co-author trailers
+    None,
 }
 
 /// Configuration for PR command
@@ -60,6 +245,25 @@ pub struct CommitConfig {
 pub struct PrConfig {
     pub prompt: Option<String>,
     pub no_confirm: Option<bool>,
+    /// Summarize test file changes for the Testing section (default: true)
+    pub analyze_tests: Option<bool>,
+    /// Path patterns identifying test files (e.g. `tests/`, `*_test.rs`)
+    pub test_patterns: Option<Vec<String>>,
+    /// Always produce a separate title and body (same as `--split`)
+    pub split_title_body: Option<bool>,
+    /// Regex extracting a ticket id from the branch name, referenced in the description
+    pub ticket_pattern: Option<String>,
+    /// Add conventions for the repository's primary language (default: true)
+    pub language_hints: Option<bool>,
+    /// Tone of the description: terse, formal, friendly, or a free-form hint
+    pub tone: Option<String>,
+    /// Number of reviewers suggested by `--reviewers` (default: 3)
+    pub reviewer_count: Option<usize>,
+    /// Names or emails never suggested as reviewers (you are always excluded)
+    pub reviewer_exclude: Option<Vec<String>>,
+    /// Description sections, in order: Summary, Changes, Why, Testing, Notes,
+    /// References (default: all but References)
+    pub sections: Option<Vec<String>>,
 }
 
 /// Configuration for merge command
@@ -67,6 +271,10 @@ pub struct PrConfig {
 pub struct MergeConfig {
     pub prompt: Option<String>,
     pub no_confirm: Option<bool>,
+    /// Tone of the merge summary: terse, formal, friendly, or a free-form hint
+    pub tone: Option<String>,
+    /// Branches (`*` and `?` wildcards allowed) that merge refuses to run on without --force
+    pub protected_branches: Option<Vec<String>>,
 }
 
 /// Configuration for init command
@@ -74,6 +282,14 @@ pub struct MergeConfig {
 pub struct InitConfig {
     pub prompt: Option<String>,
     pub no_confirm: Option<bool>,
+    /// Always scaffold without asking questions (same as --non-interactive)
+    pub non_interactive: Option<bool>,
+    /// Default package manager for non-interactive scaffolding
+    pub package_manager: Option<String>,
+    /// Add a GitHub Actions CI workflow when scaffolding non-interactively
+    pub ci: Option<bool>,
+    /// Default license (SPDX identifier) for non-interactive scaffolding
+    pub license: Option<String>,
 }
 
 /// Configuration for ignore command
@@ -83,42 +299,124 @@ pub struct IgnoreConfig {
     pub no_confirm: Option<bool>,
 }
 
+/// Configuration for amend-files command
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct AmendFilesConfig {
+    pub prompt: Option<String>,
+    pub no_confirm: Option<bool>,
+}
+
+/// Configuration for reword command
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct RewordConfig {
+    pub prompt: Option<String>,
+    pub no_confirm: Option<bool>,
+}
+
+/// Configuration for resolve command
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ResolveConfig {
+    pub prompt: Option<String>,
+    pub no_confirm: Option<bool>,
+}
+
+/// Configuration for ship command
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ShipConfig {
+    pub no_confirm: Option<bool>,
+    /// Remote the branch is pushed to (default: origin)
+    pub remote: Option<String>,
+}
+
+/// Configuration for prepush command
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct PrepushConfig {
+    /// Files larger than this block the push (default: 5)
+    pub max_file_size_mb: Option<u64>,
+    /// Block pushes containing breaking changes instead of only warning (default: false)
+    pub block_breaking_changes: Option<bool>,
+}
+
 impl Config {
     /// Load configuration from the standard config paths
     pub fn load() -> Result<Self> {
-        // Try loading in this order:
-        // 1. .git-ai.yaml in current directory (repo-specific)
+        // Layer configs in this order, later layers winning field-by-field:
+        // 1. Default configuration
         // 2. ~/.config/git-ai/config.yaml (user-specific)
-        // 3. Default configuration
-
-        if let Ok(config) = Self::load_from_path(&PathBuf::from(".git-ai.yaml")) {
-            return Ok(config);
-        }
+        // 3. .git-ai.yaml in each directory from the repo root down to the
+        //    current directory (closer directories win)
 
+        let mut paths = Vec::new();
         if let Some(user_config_path) = Self::user_config_path() {
-            if let Ok(config) = Self::load_from_path(&user_config_path) {
-                return Ok(config);
-            }
+            paths.push(user_config_path);
         }
+        paths.extend(Self::repo_config_paths());
 
-        Ok(Self::default())
+        Self::load_from_paths(&paths)
     }
 
-    /// Load configuration from a specific path
-    pub fn load_from_path(path: &PathBuf) -> Result<Self> {
-        if !path.exists() {
-            anyhow::bail!("Config file does not exist: {}", path.display());
+    /// Candidate `.git-ai.yaml` paths for the current directory, ordered from
+    /// the repository root down to the current directory
+    pub fn repo_config_paths() -> Vec<PathBuf> {
+        match std::env::current_dir() {
+            Ok(dir) => repo_config_paths_from(&dir),
+            Err(_) => vec![PathBuf::from(REPO_CONFIG_FILE)],
         }
+    }
 
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    /// Load configuration by layering the given files in order, skipping any
+    /// that don't exist
+    pub fn load_from_paths(paths: &[PathBuf]) -> Result<Self> {
+        let mut merged = Value::Mapping(Mapping::new());
 
-        let config: Config = serde_yaml::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        for path in paths.iter().filter(|path| path.exists()) {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+            let layer: Value = serde_yaml::from_str(&content)
+                .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+            merge_yaml(&mut merged, layer);
+        }
+
+        let mut config: Config =
+            serde_yaml::from_value(merged).context("Failed to parse merged configuration")?;
+        for command in config.clear_empty_prompts() {
+            eprintln!(
+                "{}Ignoring empty `commands.{}.prompt`; using the built-in prompt",
+                icon(Icon::Warn),
+                command
+            );
+        }
 
         Ok(config)
     }
 
+    /// Treat blank custom prompts as unset so the built-in prompt is used.
+    /// Returns the commands whose prompt was cleared.
+    fn clear_empty_prompts(&mut self) -> Vec<&'static str> {
+        let commands = &mut self.commands;
+        let prompts = [
+            ("commit", &mut commands.commit.prompt),
+            ("pr", &mut commands.pr.prompt),
+            ("merge", &mut commands.merge.prompt),
+            ("init", &mut commands.init.prompt),
+            ("ignore", &mut commands.ignore.prompt),
+            ("amend_files", &mut commands.amend_files.prompt),
+            ("reword", &mut commands.reword.prompt),
+            ("resolve", &mut commands.resolve.prompt),
+        ];
+
+        prompts
+            .into_iter()
+            .filter(|(_, prompt)| prompt.as_deref().is_some_and(|p| p.trim().is_empty()))
+            .map(|(command, prompt)| {
+                *prompt = None;
+                command
+            })
+            .collect()
+    }
+
     /// Get the user configuration path
     pub fn user_config_path() -> Option<PathBuf> {
         if let Some(config_dir) = dirs::config_dir() {
@@ -133,32 +431,144 @@ impl Config {
 
     /// Create a sample configuration file
     pub fn create_sample_config() -> Result<String> {
-        let sample = Config {
-            behavior: BehaviorConfig { verbose: false },
+        let sample = serde_yaml::to_string(&Self::sample())
+            .context("Failed to serialize sample configuration")?;
+        Ok(comment_out_examples(&sample, SAMPLE_EXAMPLES))
+    }
+
+    /// Sample configuration. Optional policies stay unset, so a generated file
+    /// changes nothing until the user enables them; they are shown as
+    /// commented-out [`SAMPLE_EXAMPLES`] instead.
+    fn sample() -> Self {
+        let mut config = Self::schema();
+        let commands = &mut config.commands;
+        commands.commit.prompt = None;
+        commands.pr.prompt = None;
+        commands.merge.prompt = None;
+        commands.init.prompt = None;
+        commands.ignore.prompt = None;
+        commands.amend_files.prompt = None;
+        commands.reword.prompt = None;
+        commands.resolve.prompt = None;
+        config.behavior.default_command = None;
+        config.context.always_include = None;
+        let commit = &mut config.commands.commit;
+        commit.style_anchor_commits = None;
+        commit.allowed_types = None;
+        commit.ticket_pattern = None;
+        commit.oneline_threshold = None;
+        commit.tone = None;
+        commit.footers = None;
+        config.commands.pr.ticket_pattern = None;
+        config.commands.pr.tone = None;
+        config
+    }
+
+    /// Configuration with every setting spelled out, used to check the keys
+    /// given to `--set` and `--unset`
+    fn schema() -> Self {
+        Config {
+            version: CONFIG_VERSION,
+            behavior: BehaviorConfig {
+                verbose: false,
+                emoji: true,
+                default_command: Some("commit".to_string()),
+                color: true,
+                preview_width: Some(72),
+            },
+            agent: AgentConfig {
+                binary: None,
+                save_raw_responses: Some(false),
+                subcommand: Some(crate::cursor_agent::DEFAULT_SUBCOMMAND.to_string()),
+                print_flag: Some(crate::cursor_agent::DEFAULT_PRINT_FLAG.to_string()),
+            },
+            git: GitConfig { binary: None },
+            context: ContextConfig {
+                exclude: Some(
+                    crate::pattern::DEFAULT_EXCLUDES
+                        .iter()
+                        .map(|pattern| pattern.to_string())
+                        .collect(),
+                ),
+                max_line_length: Some(crate::context::DEFAULT_MAX_LINE_LENGTH),
+                always_include: Some(vec!["COMMIT_CONVENTIONS.md".to_string()]),
+            },
             commands: CommandConfigs {
                 commit: CommitConfig {
                     prompt: Some(
                         "Custom commit prompt (optional - overrides built-in prompt)".to_string(),
                     ),
                     no_confirm: Some(false),
+                    co_authors: Some(CoAuthorSource::Auto),
+                    style_anchor_commits: Some(3),
+                    grouping: Some(CommitGrouping::Atomic),
+                    allowed_types: Some(vec![
+                        "feat".to_string(),
+                        "fix".to_string(),
+                        "docs".to_string(),
+                        "chore".to_string(),
+                    ]),
+                    ticket_pattern: Some("[A-Z][A-Z0-9]+-[0-9]+".to_string()),
+                    generated_by_trailer: Some(false),
+                    provenance_trailers: Some(false),
+                    oneline_threshold: Some(3),
+                    language_hints: Some(true),
+                    body_bullets: Some(2),
+                    include_body: Some(true),
+                    tone: Some("terse".to_string()),
+                    pending_markers: Some(vec![
+                        "TODO".to_string(),
+                        "FIXME".to_string(),
+                        "XXX".to_string(),
+                    ]),
+                    footers: Some(vec!["Refs: {branch}".to_string()]),
+                    large_file_mb: Some(5),
+                    warn_binary_files: Some(true),
+                    auto_commit: Some(true),
+                    staged_content_kb: Some(0),
+                    spellcheck: Some(false),
                 },
                 pr: PrConfig {
                     prompt: Some(
                         "Custom PR prompt (optional - overrides built-in prompt)".to_string(),
                     ),
                     no_confirm: Some(false),
+                    analyze_tests: Some(true),
+                    test_patterns: Some(vec!["tests/".to_string(), "*_test.rs".to_string()]),
+                    split_title_body: Some(false),
+                    ticket_pattern: Some("[A-Z][A-Z0-9]+-[0-9]+".to_string()),
+                    language_hints: Some(true),
+                    tone: Some("formal".to_string()),
+                    reviewer_count: Some(3),
+                    reviewer_exclude: Some(vec!["bot@example.com".to_string()]),
+                    sections: Some(
+                        crate::commands::pr::DEFAULT_PR_SECTIONS
+                            .iter()
+                            .map(|section| section.to_string())
+                            .collect(),
+                    ),
                 },
                 merge: MergeConfig {
                     prompt: Some(
                         "Custom merge prompt (optional - overrides built-in prompt)".to_string(),
                     ),
                     no_confirm: Some(false),
+                    tone: None,
+                    protected_branches: Some(vec![
+                        "main".to_string(),
+                        "master".to_string(),
+                        "release/*".to_string(),
+                    ]),
                 },
                 init: InitConfig {
                     prompt: Some(
                         "Custom init prompt (optional - overrides built-in prompt)".to_string(),
                     ),
                     no_confirm: Some(false),
+                    non_interactive: Some(false),
+                    package_manager: None,
+                    ci: Some(true),
+                    license: Some("MIT".to_string()),
                 },
                 ignore: IgnoreConfig {
                     prompt: Some(
@@ -166,10 +576,376 @@ impl Config {
                     ),
                     no_confirm: Some(false),
                 },
+                amend_files: AmendFilesConfig {
+                    prompt: Some(
+                        "Custom amend-files prompt (optional - overrides built-in prompt)"
+                            .to_string(),
+                    ),
+                    no_confirm: Some(false),
+                },
+                prepush: PrepushConfig {
+                    max_file_size_mb: Some(5),
+                    block_breaking_changes: Some(false),
+                },
+                reword: RewordConfig {
+                    prompt: Some(
+                        "Custom reword prompt (optional - overrides built-in prompt)".to_string(),
+                    ),
+                    no_confirm: Some(false),
+                },
+                resolve: ResolveConfig {
+                    prompt: Some(
+                        "Custom resolve prompt (optional - overrides built-in prompt)".to_string(),
+                    ),
+                    no_confirm: Some(false),
+                },
+                ship: ShipConfig {
+                    no_confirm: Some(false),
+                    remote: Some(crate::commands::ship::DEFAULT_REMOTE.to_string()),
+                },
             },
+        }
+    }
+
+    /// Set a dotted `key` (e.g. `commands.commit.tone`) in the config file at
+    /// `path`, creating the file if needed. `value` is parsed as YAML, so
+    /// `true`, `3`, and `[feat, fix]` keep their types.
+    pub fn set_value(path: &Path, key: &str, value: &str) -> Result<()> {
+        let segments = known_key(key)?;
+        let new_value: Value =
+            serde_yaml::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+
+        let mut config = read_config_value(path)?;
+        let mut current = &mut config;
+        for segment in segments {
+            if !current.is_mapping() {
+                *current = Value::Mapping(Mapping::new());
+            }
+            let Value::Mapping(map) = current else {
+                unreachable!("just replaced with a mapping");
+            };
+            current = map
+                .entry(Value::from(segment))
+                .or_insert(Value::Mapping(Mapping::new()));
+        }
+        *current = new_value;
+
+        write_config_value(path, &config)
+            .with_context(|| format!("Invalid value for `{}`: {}", key, value))
+    }
+
+    /// Remove a dotted `key` from the config file at `path`. Returns whether
+    /// the key was present.
+    pub fn unset_value(path: &Path, key: &str) -> Result<bool> {
+        let segments = known_key(key)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        let mut config = read_config_value(path)?;
+        let removed = remove_key(&mut config, &segments);
+        if removed {
+            write_config_value(path, &config)?;
+        }
+        Ok(removed)
+    }
+
+    /// Upgrade a config file to the current format version, keeping a `.bak`
+    /// copy of the original. Returns the version migrated from, or `None` if
+    /// the file was already current.
+    pub fn migrate_file(path: &Path) -> Result<Option<u32>> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        let mut value: Value = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+        let Some(from_version) = migrate_yaml(&mut value)? else {
+            return Ok(None);
         };
 
-        serde_yaml::to_string(&sample).context("Failed to serialize sample configuration")
+        // Make sure the migrated file is something we can actually load
+        serde_yaml::from_value::<Config>(value.clone()).with_context(|| {
+            format!("Config file is invalid after migration: {}", path.display())
+        })?;
+
+        let backup_path = PathBuf::from(format!("{}.bak", path.display()));
+        std::fs::write(&backup_path, &content)
+            .with_context(|| format!("Failed to write backup: {}", backup_path.display()))?;
+
+        let migrated =
+            serde_yaml::to_string(&value).context("Failed to serialize migrated configuration")?;
+        std::fs::write(path, migrated)
+            .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+
+        Ok(Some(from_version))
+    }
+}
+
+/// `.git-ai.yaml` paths in every directory from the repository containing
+/// `dir` down to `dir` itself. Outside a repository only `dir` is used.
+fn repo_config_paths_from(dir: &Path) -> Vec<PathBuf> {
+    let Some(depth) = dir
+        .ancestors()
+        .position(|ancestor| ancestor.join(".git").exists())
+    else {
+        return vec![dir.join(REPO_CONFIG_FILE)];
+    };
+
+    let mut paths: Vec<PathBuf> = dir
+        .ancestors()
+        .take(depth + 1)
+        .map(|ancestor| ancestor.join(REPO_CONFIG_FILE))
+        .collect();
+    paths.reverse();
+    paths
+}
+
+/// Optional settings shown as commented-out examples in the sample config,
+/// by dotted key (`*` matches any one segment)
+const SAMPLE_EXAMPLES: &[(&str, &str)] = &[
+    (
+        "commands.*.prompt",
+        "\"Custom prompt\" # Replaces the built-in prompt for this command",
+    ),
+    (
+        "behavior.default_command",
+        "commit # Run by a bare `git ai` instead of printing help",
+    ),
+    (
+        "context.always_include",
+        "[COMMIT_CONVENTIONS.md] # Files, relative to the repository root, sent with every command",
+    ),
+    (
+        "commands.commit.style_anchor_commits",
+        "3 # Show the last N commit messages as a style example",
+    ),
+    (
+        "commands.commit.allowed_types",
+        "[feat, fix, docs, chore] # Conventional commit types to use (default: the standard set)",
+    ),
+    (
+        "commands.commit.ticket_pattern",
+        "\"[A-Z][A-Z0-9]+-[0-9]+\" # Add `Refs: JIRA-123` from branch feature/JIRA-123-...",
+    ),
+    (
+        "commands.commit.oneline_threshold",
+        "3 # Changes of at most 3 lines get a subject-only message",
+    ),
+    (
+        "commands.commit.tone",
+        "terse # terse | formal | friendly | neutral, or any free-form hint",
+    ),
+    (
+        "commands.commit.footers",
+        "[\"Refs: {branch}\"] # Extra trailers; {user.name}, {user.email}, and {branch} are filled in",
+    ),
+    (
+        "commands.pr.ticket_pattern",
+        "\"[A-Z][A-Z0-9]+-[0-9]+\" # Reference the branch's ticket in the description",
+    ),
+    ("commands.pr.tone", "formal # Also available for merge"),
+];
+
+/// Replace each unset (`null`) setting listed in `examples` with a commented
+/// example line, tracking the dotted key of each line from its indentation
+fn comment_out_examples(yaml: &str, examples: &[(&str, &str)]) -> String {
+    let mut path: Vec<&str> = Vec::new();
+    let mut lines = Vec::new();
+
+    for line in yaml.lines() {
+        let content = line.trim_start();
+        let indent = &line[..line.len() - content.len()];
+        let Some((key, value)) = content.split_once(':') else {
+            lines.push(line.to_string());
+            continue;
+        };
+        if key.starts_with('-') || key.contains(' ') {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        path.truncate(indent.len() / 2);
+        path.push(key);
+        let matches = |name: &str| {
+            let pattern: Vec<&str> = name.split('.').collect();
+            pattern.len() == path.len()
+                && pattern
+                    .iter()
+                    .zip(&path)
+                    .all(|(expected, segment)| *expected == "*" || expected == segment)
+        };
+        match examples.iter().find(|(name, _)| matches(name)) {
+            Some((_, example)) if value.trim() == "null" => {
+                lines.push(format!("{}# {}: {}", indent, key, example))
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Split a dotted key, rejecting keys that are not config settings
+fn known_key(key: &str) -> Result<Vec<&str>> {
+    let segments: Vec<&str> = key.split('.').collect();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        anyhow::bail!("Invalid config key: {}", key);
+    }
+
+    let mut schema = serde_yaml::to_value(Config::schema())
+        .context("Failed to serialize configuration schema")?;
+    for segment in &segments {
+        schema = match schema.get(*segment) {
+            Some(value) => value.clone(),
+            None => anyhow::bail!("Unknown config key: {}", key),
+        };
+    }
+
+    Ok(segments)
+}
+
+/// Remove a dotted key from a raw config, dropping sections it leaves empty
+fn remove_key(value: &mut Value, segments: &[&str]) -> bool {
+    let Value::Mapping(map) = value else {
+        return false;
+    };
+    let Some((first, rest)) = segments.split_first() else {
+        return false;
+    };
+
+    if rest.is_empty() {
+        return map.remove(*first).is_some();
+    }
+
+    let Some(child) = map.get_mut(*first) else {
+        return false;
+    };
+    let removed = remove_key(child, rest);
+    if removed && child.as_mapping().is_some_and(Mapping::is_empty) {
+        map.remove(*first);
+    }
+    removed
+}
+
+/// Read a config file as raw YAML, or a fresh versioned mapping if it does not exist
+fn read_config_value(path: &Path) -> Result<Value> {
+    if !path.exists() {
+        let mut map = Mapping::new();
+        map.insert(Value::from("version"), Value::from(CONFIG_VERSION));
+        return Ok(Value::Mapping(map));
+    }
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let value: Value = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+    Ok(match value {
+        Value::Null => Value::Mapping(Mapping::new()),
+        value => value,
+    })
+}
+
+/// Check that a raw config still loads, then write it to `path`
+fn write_config_value(path: &Path, value: &Value) -> Result<()> {
+    serde_yaml::from_value::<Config>(value.clone())?;
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let content = serde_yaml::to_string(value).context("Failed to serialize configuration")?;
+    std::fs::write(path, content)
+        .with_context(|| format!("Failed to write config file: {}", path.display()))
+}
+
+/// Apply all pending migrations to a raw config value. Returns the version
+/// migrated from, or `None` if the value was already current.
+fn migrate_yaml(value: &mut Value) -> Result<Option<u32>> {
+    if value.is_null() {
+        *value = Value::Mapping(Mapping::new());
+    }
+
+    let Value::Mapping(map) = value else {
+        anyhow::bail!("Config file must be a YAML mapping");
+    };
+
+    // Files written before versioning was introduced have no `version` key
+    let from_version = match map.get("version") {
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .context("Config `version` must be a non-negative integer")?,
+        None => 0,
+    };
+
+    if from_version > CONFIG_VERSION {
+        anyhow::bail!(
+            "Config version {} is newer than this git-ai supports ({}); upgrade git-ai",
+            from_version,
+            CONFIG_VERSION
+        );
+    }
+
+    if from_version == CONFIG_VERSION {
+        return Ok(None);
+    }
+
+    for migration in &MIGRATIONS[from_version as usize..] {
+        migration(map);
+    }
+    map.insert(Value::from("version"), Value::from(CONFIG_VERSION));
+
+    Ok(Some(from_version))
+}
+
+/// Version 1 introduced the `version` field. Unversioned files made by
+/// `config --init` hold placeholder prompts (e.g. "Custom commit prompt
+/// (optional - overrides built-in prompt)") that replace the built-in ones, so
+/// they are dropped; prompts the user wrote are kept.
+fn migrate_v0_to_v1(config: &mut Mapping) {
+    let Some(Value::Mapping(commands)) = config.get_mut("commands") else {
+        return;
+    };
+
+    for (_, command) in commands.iter_mut() {
+        let Value::Mapping(command) = command else {
+            continue;
+        };
+        let placeholder = command
+            .get("prompt")
+            .and_then(Value::as_str)
+            .is_some_and(|prompt| {
+                prompt.starts_with("Custom ")
+                    && prompt.ends_with(" prompt (optional - overrides built-in prompt)")
+            });
+        if placeholder {
+            command.remove("prompt");
+        }
+    }
+}
+
+/// Deep-merge `overlay` into `base`. Mappings are merged key-by-key; any other
+/// value in `overlay` replaces the one in `base`. An empty overlay is ignored.
+fn merge_yaml(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (_, Value::Null) => {}
+        (Value::Mapping(base_map), Value::Mapping(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) if existing.is_mapping() && value.is_mapping() => {
+                        merge_yaml(existing, value)
+                    }
+                    _ => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
     }
 }
 
@@ -185,6 +961,11 @@ mod tests {
         assert!(!config.behavior.verbose);
     }
 
+    #[test]
+    fn test_default_config_enables_emoji() {
+        assert!(Config::default().behavior.emoji);
+    }
+
     #[test]
     fn test_sample_config_generation() {
         let sample = Config::create_sample_config().unwrap();
@@ -194,6 +975,22 @@ mod tests {
         assert!(sample.contains("verbose"));
     }
 
+    #[test]
+    fn test_sample_config_leaves_optional_policies_unset() {
+        let sample = Config::create_sample_config().unwrap();
+        assert!(sample.contains("    # footers: [\"Refs: {branch}\"]"));
+
+        let config: Config = serde_yaml::from_str(&sample).unwrap();
+        assert!(sample.contains("    # prompt: \"Custom prompt\""));
+        assert_eq!(config.commands.commit.prompt, None);
+        assert_eq!(config.behavior.default_command, None);
+        assert_eq!(config.context.always_include, None);
+        assert_eq!(config.commands.commit.allowed_types, None);
+        assert_eq!(config.commands.commit.tone, None);
+        assert_eq!(config.commands.commit.footers, None);
+        assert!(known_key("commands.commit.footers").is_ok());
+    }
+
     #[test]
     fn test_config_loading_from_path() {
         let temp_dir = tempdir().unwrap();
@@ -211,7 +1008,7 @@ commands:
 
         fs::write(&config_path, test_config).unwrap();
 
-        let config = Config::load_from_path(&config_path).unwrap();
+        let config = Config::load_from_paths(&[config_path]).unwrap();
         assert!(config.behavior.verbose);
         assert_eq!(config.commands.commit.no_confirm, Some(true));
         assert_eq!(
@@ -220,6 +1017,221 @@ commands:
         );
     }
 
+    #[test]
+    fn test_repo_config_layers_over_user_config() {
+        let temp_dir = tempdir().unwrap();
+        let user_path = temp_dir.path().join("user.yaml");
+        let repo_path = temp_dir.path().join("repo.yaml");
+
+        fs::write(
+            &user_path,
+            r#"
+behavior:
+  verbose: true
+commands:
+  commit:
+    prompt: "User commit prompt"
+    no_confirm: true
+"#,
+        )
+        .unwrap();
+        fs::write(
+            &repo_path,
+            r#"
+commands:
+  commit:
+    no_confirm: false
+  pr:
+    prompt: "Repo PR prompt"
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_paths(&[user_path, repo_path]).unwrap();
+
+        // Repo wins where it sets a field, user values survive elsewhere
+        assert!(config.behavior.verbose);
+        assert_eq!(config.commands.commit.no_confirm, Some(false));
+        assert_eq!(
+            config.commands.commit.prompt.as_deref(),
+            Some("User commit prompt")
+        );
+        assert_eq!(config.commands.pr.prompt.as_deref(), Some("Repo PR prompt"));
+    }
+
+    #[test]
+    fn test_empty_and_missing_config_layers_are_ignored() {
+        let temp_dir = tempdir().unwrap();
+        let user_path = temp_dir.path().join("user.yaml");
+        let empty_path = temp_dir.path().join("empty.yaml");
+        let missing_path = temp_dir.path().join("missing.yaml");
+
+        fs::write(&user_path, "behavior:\n  verbose: true\n").unwrap();
+        fs::write(&empty_path, "").unwrap();
+
+        let config = Config::load_from_paths(&[user_path, empty_path, missing_path]).unwrap();
+        assert!(config.behavior.verbose);
+    }
+
+    #[test]
+    fn test_repo_config_paths_cascade_from_repo_root() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().join("repo");
+        let service = root.join("services").join("api");
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(&service).unwrap();
+
+        assert_eq!(
+            repo_config_paths_from(&service),
+            vec![
+                root.join(REPO_CONFIG_FILE),
+                root.join("services").join(REPO_CONFIG_FILE),
+                service.join(REPO_CONFIG_FILE),
+            ]
+        );
+
+        // Outside a repository only the current directory is considered
+        let outside = temp_dir.path().join("plain");
+        fs::create_dir_all(&outside).unwrap();
+        assert_eq!(
+            repo_config_paths_from(&outside),
+            vec![outside.join(REPO_CONFIG_FILE)]
+        );
+    }
+
+    #[test]
+    fn test_subdirectory_config_overrides_root_config() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let service = root.join("service");
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(&service).unwrap();
+
+        fs::write(
+            root.join(REPO_CONFIG_FILE),
+            "commands:\n  commit:\n    allowed_types: [feat, fix]\n    tone: formal\n",
+        )
+        .unwrap();
+        fs::write(
+            service.join(REPO_CONFIG_FILE),
+            "commands:\n  commit:\n    tone: terse\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_paths(&repo_config_paths_from(&service)).unwrap();
+        assert_eq!(config.commands.commit.tone.as_deref(), Some("terse"));
+        assert_eq!(
+            config.commands.commit.allowed_types,
+            Some(vec!["feat".to_string(), "fix".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_set_and_unset_value() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("nested").join(REPO_CONFIG_FILE);
+
+        Config::set_value(&path, "commands.commit.tone", "terse").unwrap();
+        Config::set_value(&path, "commands.commit.allowed_types", "[feat, fix]").unwrap();
+        Config::set_value(&path, "behavior.verbose", "true").unwrap();
+
+        let config = Config::load_from_paths(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.commands.commit.tone.as_deref(), Some("terse"));
+        assert_eq!(
+            config.commands.commit.allowed_types,
+            Some(vec!["feat".to_string(), "fix".to_string()])
+        );
+        assert!(config.behavior.verbose);
+
+        assert!(Config::unset_value(&path, "commands.commit.tone").unwrap());
+        assert!(!Config::unset_value(&path, "commands.commit.tone").unwrap());
+        let config = Config::load_from_paths(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(config.commands.commit.tone, None);
+        assert!(config.behavior.verbose);
+    }
+
+    #[test]
+    fn test_set_value_rejects_bad_keys_and_values() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(REPO_CONFIG_FILE);
+
+        assert!(Config::set_value(&path, "commands.commit.model", "gpt-4o").is_err());
+        assert!(Config::set_value(&path, "commands..tone", "terse").is_err());
+        assert!(Config::set_value(&path, "behavior.verbose", "loud").is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_co_author_source_parsing() {
+        let config: Config =
+            serde_yaml::from_str("commands:\n  commit:\n    co_authors: pairing-file\n").unwrap();
+        assert_eq!(
+            config.commands.commit.co_authors,
+            Some(CoAuthorSource::PairingFile)
+        );
+    }
+
+    #[test]
+    fn test_migrate_unversioned_config() {
+        let mut value: Value = serde_yaml::from_str("behavior:\n  verbose: true\n").unwrap();
+
+        assert_eq!(migrate_yaml(&mut value).unwrap(), Some(0));
+        assert_eq!(value["version"].as_u64(), Some(CONFIG_VERSION as u64));
+        assert_eq!(value["behavior"]["verbose"].as_bool(), Some(true));
+
+        // Running again is a no-op
+        assert_eq!(migrate_yaml(&mut value).unwrap(), None);
+    }
+
+    #[test]
+    fn test_migrate_v0_drops_placeholder_prompts() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        // As written by `config --init` before config files were versioned
+        fs::write(
+            &config_path,
+            "behavior:\n  verbose: false\ncommands:\n  commit:\n    prompt: Custom commit prompt \
+             (optional - overrides built-in prompt)\n    no_confirm: false\n  pr:\n    prompt: \
+             Describe the change for reviewers\n",
+        )
+        .unwrap();
+
+        assert_eq!(Config::migrate_file(&config_path).unwrap(), Some(0));
+
+        let config = Config::load_from_paths(std::slice::from_ref(&config_path)).unwrap();
+        assert_eq!(config.commands.commit.prompt, None);
+        assert_eq!(config.commands.commit.no_confirm, Some(false));
+        assert_eq!(
+            config.commands.pr.prompt.as_deref(),
+            Some("Describe the change for reviewers")
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_config() {
+        let mut value: Value = serde_yaml::from_str("version: 999\n").unwrap();
+        assert!(migrate_yaml(&mut value).is_err());
+    }
+
+    #[test]
+    fn test_migrate_file_writes_backup() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        fs::write(&config_path, "behavior:\n  verbose: true\n").unwrap();
+
+        assert_eq!(Config::migrate_file(&config_path).unwrap(), Some(0));
+
+        let backup = fs::read_to_string(temp_dir.path().join("config.yaml.bak")).unwrap();
+        assert_eq!(backup, "behavior:\n  verbose: true\n");
+
+        let config = Config::load_from_paths(std::slice::from_ref(&config_path)).unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert!(config.behavior.verbose);
+
+        assert_eq!(Config::migrate_file(&config_path).unwrap(), None);
+    }
+
     #[test]
     fn test_prompt_fallbacks() {
         let config = Config::default();
@@ -230,6 +1242,22 @@ commands:
         assert!(config.commands.merge.prompt.is_none());
     }
 
+    #[test]
+    fn test_empty_prompts_fall_back_to_built_in() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+        fs::write(
+            &path,
+            "commands:\n  commit:\n    prompt: \"\"\n  pr:\n    prompt: \"  \\n\"\n  merge:\n    prompt: Custom\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_paths(&[path]).unwrap();
+        assert!(config.commands.commit.prompt.is_none());
+        assert!(config.commands.pr.prompt.is_none());
+        assert_eq!(config.commands.merge.prompt.as_deref(), Some("Custom"));
+    }
+
     #[test]
     fn test_prompt_overrides() {
         let mut config = Config::default();
@@ -0,0 +1,594 @@
+use crate::git;
+use crate::terminal::{icon, Icon};
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Largest file that will be inlined into a prompt
+pub const MAX_INCLUDED_FILE_BYTES: u64 = 100 * 1024;
+
+/// Read files requested as extra context and format them as a prompt section
+pub fn included_files_section(paths: &[PathBuf]) -> Result<Option<String>> {
+    if paths.is_empty() {
+        return Ok(None);
+    }
+
+    let files = paths
+        .iter()
+        .map(|path| {
+            read_text_file(path, MAX_INCLUDED_FILE_BYTES, "Included file")
+                .map(|content| format_file(path, &content))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(format!(
+        "Included files: the user provided these files as additional context for this change.\n\n{}",
+        files.join("\n\n")
+    )))
+}
+
+/// Files from the `context.always_include` setting, set once at startup
+static ALWAYS_INCLUDE: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Add these files, relative to the repository root, to every AI prompt
+pub fn set_always_include(paths: Vec<String>) {
+    let _ = ALWAYS_INCLUDE.set(paths);
+}
+
+/// Prompt section with the files from `context.always_include`. Files that
+/// are missing or cannot be inlined are skipped with a warning on stderr, so
+/// `--json` output stays parseable.
+pub fn always_included_section() -> Option<String> {
+    let paths = ALWAYS_INCLUDE.get().filter(|paths| !paths.is_empty())?;
+    let root = git::repo_root().unwrap_or_else(|_| PathBuf::from("."));
+    conventions_section(&root, paths)
+}
+
+/// Format the readable files among `paths`, relative to `root`, warning about the rest
+fn conventions_section(root: &Path, paths: &[String]) -> Option<String> {
+    let files: Vec<String> = paths
+        .iter()
+        .filter_map(|path| {
+            let full = root.join(path);
+            match read_text_file(&full, MAX_INCLUDED_FILE_BYTES, "Always-included file") {
+                Ok(content) => Some(format_file(Path::new(path), &content)),
+                Err(e) => {
+                    eprintln!("{}{}", icon(Icon::Warn), e);
+                    None
+                }
+            }
+        })
+        .collect();
+    if files.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "Project conventions: the repository requires these files to be considered by every \
+         command. Follow the rules they set.\n\n{}",
+        files.join("\n\n")
+    ))
+}
+
+/// Built-in tones and the instruction each one adds to the prompt
+const TONES: &[(&str, &str)] = &[
+    (
+        "terse",
+        "Be terse: use as few words as possible, no filler, no adjectives.",
+    ),
+    (
+        "formal",
+        "Be formal and polished, suitable for a public audience; avoid slang and jokes.",
+    ),
+    (
+        "friendly",
+        "Be friendly and approachable while staying clear and accurate.",
+    ),
+    (
+        "neutral",
+        "Be neutral and factual; describe the changes without embellishment.",
+    ),
+];
+
+/// Prompt section for the configured tone. Known tones map to a fixed
+/// instruction; anything else is passed through as a free-form hint.
+pub fn tone_section(tone: Option<&str>) -> Option<String> {
+    let tone = tone.map(str::trim).filter(|tone| !tone.is_empty())?;
+
+    let instruction = TONES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(tone))
+        .map(|(_, instruction)| instruction.to_string())
+        .unwrap_or_else(|| format!("Write in this tone: {}.", tone));
+
+    Some(format!("Tone: {}", instruction))
+}
+
+/// Markers for unfinished work flagged when none are configured
+pub const DEFAULT_PENDING_MARKERS: &[&str] = &["TODO", "FIXME", "XXX"];
+
+/// Most marker lines listed in the prompt
+const MAX_PENDING_MARKERS: usize = 20;
+
+/// Prompt section listing added lines that contain a pending-work marker.
+/// Markers match as whole words, case-sensitively.
+pub fn pending_markers_section<S: AsRef<str>>(
+    added_lines: &[(String, String)],
+    markers: &[S],
+) -> Option<String> {
+    if markers.is_empty() {
+        return None;
+    }
+
+    let alternatives = markers
+        .iter()
+        .map(|marker| regex::escape(marker.as_ref()))
+        .collect::<Vec<_>>()
+        .join("|");
+    let regex = Regex::new(&format!(r"\b(?:{})\b", alternatives)).ok()?;
+
+    let found: Vec<String> = added_lines
+        .iter()
+        .filter(|(_, line)| regex.is_match(line))
+        .map(|(path, line)| format!("- {}: {}", path, line.trim()))
+        .collect();
+    if found.is_empty() {
+        return None;
+    }
+
+    let mut listed = found
+        .iter()
+        .take(MAX_PENDING_MARKERS)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n");
+    if found.len() > MAX_PENDING_MARKERS {
+        listed = format!(
+            "{}\n- ... and {} more",
+            listed,
+            found.len() - MAX_PENDING_MARKERS
+        );
+    }
+
+    Some(format!(
+        "Pending markers: these changes add unresolved markers for unfinished work. \
+         Acknowledge the incomplete work where relevant and do not describe it as complete.\n{}",
+        listed
+    ))
+}
+
+/// Format `git diff --stat` output for `--stat`, which has the AI plan from
+/// the summary and read only the diffs it needs. Returns `None` for an empty stat.
+pub fn diffstat_section(stat: &str) -> Option<String> {
+    let stat = stat.trim_end();
+    if stat.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "Diffstat: this change is large. Work from the summary below (files changed and lines \
+         added/removed) instead of reading the full diff. Read the diff of an individual file \
+         (append `-- <path>` to the diff command) only when its name and size do not make its \
+         purpose clear.\n\n{}",
+        stat
+    ))
+}
+
+/// Most files whose staged content is inlined, so a wide change cannot blow up the prompt
+pub const MAX_STAGED_CONTENT_FILES: usize = 10;
+
+/// Format the full staged content of small changed files, given as path and
+/// content pairs. Returns `None` if there are none.
+pub fn staged_content_section(files: &[(String, String)]) -> Option<String> {
+    if files.is_empty() {
+        return None;
+    }
+
+    let files = files
+        .iter()
+        .map(|(path, content)| format!("Staged file: {}\n{}", path, fenced(content, "")))
+        .collect::<Vec<_>>();
+
+    Some(format!(
+        "Staged file contents: the complete new version of these small changed files, exactly as \
+         they will be committed. These are not diffs; use them only as surrounding context for \
+         the changes in the diff, and describe the diff rather than the whole file.\n\n{}",
+        files.join("\n\n")
+    ))
+}
+
+/// Diff lines longer than this are collapsed when no limit is configured
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 2000;
+
+/// Limit from the `context.max_line_length` setting, set once at startup
+static MAX_LINE_LENGTH: OnceLock<usize> = OnceLock::new();
+
+/// Collapse diff lines longer than `max` characters instead of [`DEFAULT_MAX_LINE_LENGTH`]
+pub fn set_max_line_length(max: usize) {
+    let _ = MAX_LINE_LENGTH.set(max);
+}
+
+/// Longest diff line kept verbatim in a prompt
+pub fn max_line_length() -> usize {
+    MAX_LINE_LENGTH
+        .get()
+        .copied()
+        .unwrap_or(DEFAULT_MAX_LINE_LENGTH)
+}
+
+/// Replace diff lines longer than `max` characters, typically minified bundles,
+/// with a short note. The leading `+`, `-`, or space is kept so the line still
+/// reads as an addition, removal, or context line.
+pub fn collapse_long_lines(diff: &str, max: usize) -> String {
+    diff.split_inclusive('\n')
+        .map(|line| {
+            let content = line.trim_end_matches(['\r', '\n']);
+            let length = content.chars().count();
+            if length <= max {
+                return line.to_string();
+            }
+
+            let marker = match content.chars().next() {
+                Some(c @ ('+' | '-' | ' ')) => c.to_string(),
+                _ => String::new(),
+            };
+            let ending = &line[content.len()..];
+            format!(
+                "{}[minified/long line, {} chars changed]{}",
+                marker, length, ending
+            )
+        })
+        .collect()
+}
+
+/// Largest patch file accepted by `--patch`
+pub const MAX_PATCH_BYTES: u64 = 1024 * 1024;
+
+/// Read a patch file and format it, with the files it touches, as a prompt section
+pub fn patch_section(path: &Path) -> Result<(String, Vec<git::FileStatus>)> {
+    let patch = read_text_file(path, MAX_PATCH_BYTES, "Patch file")?;
+    let files = git::parse_patch_files(&patch);
+    if files.is_empty() {
+        anyhow::bail!("Patch file contains no file changes: {}", path.display());
+    }
+
+    let file_list = files
+        .iter()
+        .map(|f| match f.orig_path {
+            Some(ref orig) => format!("- {} {} (from {})", f.index, f.path, orig),
+            None => format!("- {} {}", f.index, f.path),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let section =
+        format!(
+        "Patch: describe the changes in the patch below instead of the branch diff. The patch is \
+         not applied to this repository, so do not run `git diff` or inspect the working tree for \
+         the changes; use the repository only for surrounding context.\n\n\
+         Files changed:\n{}\n\n{}",
+        file_list,
+        fenced(&collapse_long_lines(patch.trim_end(), max_line_length()), "diff")
+    );
+
+    Ok((section, files))
+}
+
+/// Format conflicted files, markers included, as a prompt section. `paths` are
+/// relative to the repository `root`, as git reports them. Files that cannot
+/// be inlined (deleted on one side, binary, too large) are listed by name.
+pub fn conflicted_files_section(root: &Path, paths: &[&str]) -> String {
+    let files = paths
+        .iter()
+        .map(|path| {
+            let path = Path::new(path);
+            match read_text_file(&root.join(path), MAX_INCLUDED_FILE_BYTES, "Conflicted file") {
+                Ok(content) => format_file(path, &content),
+                Err(e) => format!("File: {}\n(content not included: {})", path.display(), e),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    format!("Conflicted files:\n\n{}", files)
+}
+
+/// Placeholder for fenced file contents dropped by [`omit_file_contents`]
+const OMITTED_CONTENTS: &str =
+    "(contents omitted to fit the model's context window; read the file from the repository if needed)";
+
+/// Shorter version of `prompt` with every fenced block (included files,
+/// patches, conflicted files) replaced by a placeholder. Returns `None` when
+/// there is nothing to leave out.
+pub fn omit_file_contents(prompt: &str) -> Option<String> {
+    let mut lines = Vec::new();
+    // Length of the fence that opened the current block
+    let mut open_fence: Option<usize> = None;
+    let mut omitted = false;
+
+    for line in prompt.lines() {
+        match (open_fence, fence_length(line)) {
+            (None, Some(length)) => {
+                lines.push(OMITTED_CONTENTS);
+                omitted = true;
+                open_fence = Some(length);
+            }
+            // Only a bare fence at least as long as the opening one closes it
+            (Some(open), Some(length)) if length >= open && line.trim().len() == length => {
+                open_fence = None;
+            }
+            (None, None) => lines.push(line),
+            (Some(_), _) => {}
+        }
+    }
+
+    omitted.then(|| lines.join("\n"))
+}
+
+/// Ticket id found in the current branch name by the configured pattern
+pub fn branch_ticket(pattern: Option<&str>) -> Result<Option<String>> {
+    let Some(pattern) = pattern else {
+        return Ok(None);
+    };
+
+    match git::current_branch() {
+        Some(branch) => extract_ticket(pattern, &branch),
+        None => Ok(None),
+    }
+}
+
+/// Match `pattern` against a branch name, preferring the first capture group
+/// over the whole match so patterns can anchor on surrounding text
+fn extract_ticket(pattern: &str, branch: &str) -> Result<Option<String>> {
+    let regex = Regex::new(pattern)
+        .with_context(|| format!("Invalid ticket_pattern regex: {}", pattern))?;
+
+    Ok(regex.captures(branch).and_then(|captures| {
+        captures
+            .get(1)
+            .or_else(|| captures.get(0))
+            .map(|m| m.as_str().to_string())
+            .filter(|ticket| !ticket.is_empty())
+    }))
+}
+
+/// Read a text file, refusing anything larger than `limit` bytes or binary.
+/// `label` names the kind of file in error messages.
+fn read_text_file(path: &Path, limit: u64, label: &str) -> Result<String> {
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("Cannot read {}: {}", label.to_lowercase(), path.display()))?;
+
+    if !metadata.is_file() {
+        anyhow::bail!("{} is not a file: {}", label, path.display());
+    }
+
+    if metadata.len() > limit {
+        anyhow::bail!(
+            "{} is too large ({} KB, limit {} KB): {}",
+            label,
+            metadata.len() / 1024,
+            limit / 1024,
+            path.display()
+        );
+    }
+
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Cannot read {}: {}", label.to_lowercase(), path.display()))?;
+
+    if bytes.contains(&0) {
+        anyhow::bail!("{} looks binary: {}", label, path.display());
+    }
+
+    String::from_utf8(bytes)
+        .with_context(|| format!("{} is not valid UTF-8: {}", label, path.display()))
+}
+
+/// Format a file's content as a labeled, fenced block
+fn format_file(path: &Path, content: &str) -> String {
+    format!("File: {}\n{}", path.display(), fenced(content, ""))
+}
+
+/// Wrap `content` in a code fence longer than any backtick fence inside it,
+/// so fenced blocks in the content cannot close it early
+fn fenced(content: &str, info: &str) -> String {
+    let longest = content
+        .lines()
+        .map(|line| fence_length(line).unwrap_or(0))
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}\n{}", fence, info, content.trim_end(), fence)
+}
+
+/// Length of the backtick fence that starts `line`, if any
+fn fence_length(line: &str) -> Option<usize> {
+    let length = line.trim_start().chars().take_while(|&c| c == '`').count();
+    (length >= 3).then_some(length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_included_files_section() {
+        let temp_dir = tempdir().unwrap();
+        let doc = temp_dir.path().join("DESIGN.md");
+        fs::write(&doc, "# Design\n\nUse the builder pattern.\n").unwrap();
+
+        let section = included_files_section(&[doc]).unwrap().unwrap();
+        assert!(section.contains("DESIGN.md"));
+        assert!(section.contains("Use the builder pattern."));
+
+        assert!(included_files_section(&[]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_tone_section() {
+        assert_eq!(
+            tone_section(Some("Terse")).unwrap(),
+            "Tone: Be terse: use as few words as possible, no filler, no adjectives."
+        );
+        assert_eq!(
+            tone_section(Some("playful but precise")).unwrap(),
+            "Tone: Write in this tone: playful but precise."
+        );
+        assert!(tone_section(Some("  ")).is_none());
+        assert!(tone_section(None).is_none());
+    }
+
+    #[test]
+    fn test_extract_ticket() {
+        let pattern = "[A-Z][A-Z0-9]+-[0-9]+";
+        assert_eq!(
+            extract_ticket(pattern, "feature/JIRA-123-add-login").unwrap(),
+            Some("JIRA-123".to_string())
+        );
+        assert_eq!(extract_ticket(pattern, "main").unwrap(), None);
+        assert_eq!(
+            extract_ticket("^(?:feat|fix)/(\\d+)-", "fix/42-null-check").unwrap(),
+            Some("42".to_string())
+        );
+        assert!(extract_ticket("[unclosed", "main").is_err());
+    }
+
+    #[test]
+    fn test_included_files_rejects_binary_and_large_files() {
+        let temp_dir = tempdir().unwrap();
+        let binary = temp_dir.path().join("image.png");
+        fs::write(&binary, [0x89, b'P', b'N', b'G', 0x00, 0x01]).unwrap();
+        assert!(included_files_section(&[binary]).is_err());
+
+        let large = temp_dir.path().join("large.txt");
+        fs::write(&large, "a".repeat(MAX_INCLUDED_FILE_BYTES as usize + 1)).unwrap();
+        assert!(included_files_section(&[large]).is_err());
+
+        let missing = temp_dir.path().join("missing.txt");
+        assert!(included_files_section(&[missing]).is_err());
+    }
+
+    #[test]
+    fn test_pending_markers_section() {
+        let added = vec![
+            (
+                "src/lib.rs".to_string(),
+                "    // TODO: handle retries".to_string(),
+            ),
+            ("src/lib.rs".to_string(), "let todos = vec![];".to_string()),
+            (
+                "src/api.rs".to_string(),
+                "# FIXME broken on windows".to_string(),
+            ),
+            ("src/api.rs".to_string(), "let TODOLIST = 1;".to_string()),
+        ];
+
+        let section = pending_markers_section(&added, DEFAULT_PENDING_MARKERS).unwrap();
+        assert!(section.contains("- src/lib.rs: // TODO: handle retries"));
+        assert!(section.contains("- src/api.rs: # FIXME broken on windows"));
+        assert!(!section.contains("todos"));
+        assert!(!section.contains("TODOLIST"));
+
+        assert_eq!(pending_markers_section(&added, &["HACK"]), None);
+        assert_eq!(pending_markers_section::<&str>(&added, &[]), None);
+    }
+
+    #[test]
+    fn test_omit_file_contents() {
+        let prompt = "Task\n\nFile: notes.md\n```\nline one\nline two\n```\n\nUser context: hi";
+        assert_eq!(
+            omit_file_contents(prompt).unwrap(),
+            format!(
+                "Task\n\nFile: notes.md\n{}\n\nUser context: hi",
+                OMITTED_CONTENTS
+            )
+        );
+        assert_eq!(omit_file_contents("Task\n\nUser context: hi"), None);
+    }
+
+    #[test]
+    fn test_omit_file_contents_with_nested_fences() {
+        let readme = "# Usage\n\n```sh\ngit ai commit\n```\n\nDone.";
+        let prompt = format!(
+            "Task\n\n{}\n\nUser context: see\n```\nexample\n```",
+            format_file(Path::new("README.md"), readme)
+        );
+        assert!(prompt.contains("File: README.md\n````\n# Usage"));
+        assert_eq!(
+            omit_file_contents(&prompt).unwrap(),
+            format!(
+                "Task\n\nFile: README.md\n{}\n\nUser context: see\n{}",
+                OMITTED_CONTENTS, OMITTED_CONTENTS
+            )
+        );
+    }
+
+    #[test]
+    fn test_diffstat_section() {
+        let stat =
+            " src/lib.rs | 12 +++++++-----\n 1 file changed, 7 insertions(+), 5 deletions(-)\n";
+        let section = diffstat_section(stat).unwrap();
+        assert!(section.starts_with("Diffstat: "));
+        assert!(section.ends_with("7 insertions(+), 5 deletions(-)"));
+        assert_eq!(diffstat_section("\n"), None);
+    }
+
+    #[test]
+    fn test_conventions_section_skips_missing_files() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("COMMIT_CONVENTIONS.md"),
+            "Scopes are crate names.\n",
+        )
+        .unwrap();
+
+        let paths = vec![
+            "COMMIT_CONVENTIONS.md".to_string(),
+            "MISSING.md".to_string(),
+        ];
+        let section = conventions_section(temp_dir.path(), &paths).unwrap();
+        assert!(section.starts_with("Project conventions: "));
+        assert!(section.ends_with("File: COMMIT_CONVENTIONS.md\n```\nScopes are crate names.\n```"));
+        assert!(!section.contains("MISSING.md"));
+
+        assert_eq!(conventions_section(temp_dir.path(), &paths[1..]), None);
+    }
+
+    #[test]
+    fn test_conflicted_files_section_reads_from_root() {
+        let temp_dir = tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join("src")).unwrap();
+        fs::write(
+            temp_dir.path().join("src/lib.rs"),
+            "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\n",
+        )
+        .unwrap();
+
+        let section = conflicted_files_section(temp_dir.path(), &["src/lib.rs", "gone.rs"]);
+        assert!(section.contains("File: src/lib.rs\n```\n<<<<<<< HEAD\nours"));
+        assert!(section.contains("File: gone.rs\n(content not included: "));
+    }
+
+    #[test]
+    fn test_staged_content_section() {
+        let files = vec![("src/lib.rs".to_string(), "pub fn add() {}\n".to_string())];
+        let section = staged_content_section(&files).unwrap();
+        assert!(section.starts_with("Staged file contents: "));
+        assert!(section.contains("These are not diffs"));
+        assert!(section.ends_with("Staged file: src/lib.rs\n```\npub fn add() {}\n```"));
+        assert_eq!(staged_content_section(&[]), None);
+    }
+
+    #[test]
+    fn test_collapse_long_lines() {
+        let bundle = "x".repeat(30);
+        let diff = format!("@@ -1 +1 @@\n-{}\n+{}\n context\n", bundle, bundle);
+        assert_eq!(
+            collapse_long_lines(&diff, 20),
+            "@@ -1 +1 @@\n-[minified/long line, 31 chars changed]\n+[minified/long line, 31 chars changed]\n context\n"
+        );
+        assert_eq!(collapse_long_lines(&diff, 40), diff);
+    }
+}
@@ -1,25 +1,152 @@
+use crate::config::AgentConfig;
+use crate::terminal::{icon, Icon};
+use crate::{context, git};
 use anyhow::{Context, Result};
+use std::fmt;
+use std::path::PathBuf;
 use std::process::Command as StdCommand;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variable overriding the cursor-agent executable
+pub const AGENT_BIN_ENV: &str = "GITAI_AGENT_BIN";
+
+/// Subcommand the prompt is passed to when `agent.subcommand` is not configured
+pub const DEFAULT_SUBCOMMAND: &str = "prompt";
+
+/// Flag for printing a response when `agent.print_flag` is not configured
+pub const DEFAULT_PRINT_FLAG: &str = "--print";
+
+/// Phrases in cursor-agent's final error line meaning the prompt was too long for the model
+const CONTEXT_LENGTH_MARKERS: &[&str] = &[
+    "maximum context length",
+    "context window",
+    "prompt is too long",
+    "too many tokens",
+];
+
+/// cursor-agent rejected the prompt for exceeding the model's context window
+#[derive(Debug)]
+pub struct ContextLengthExceeded;
+
+impl fmt::Display for ContextLengthExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cursor-agent rejected the prompt: it exceeds the model's context window"
+        )
+    }
+}
+
+impl std::error::Error for ContextLengthExceeded {}
+
+/// Whether a failed cursor-agent run reports an over-length prompt. Only the
+/// last line of its error output counts, and only if it is an `error:` line,
+/// so a diff or log echoed earlier that mentions these phrases cannot match.
+fn is_context_length_error(stderr: &str) -> bool {
+    let Some(line) = stderr.lines().rev().find(|line| !line.trim().is_empty()) else {
+        return false;
+    };
+    let line = line.trim().to_lowercase();
+    line.starts_with("error")
+        && CONTEXT_LENGTH_MARKERS
+            .iter()
+            .any(|marker| line.contains(marker))
+}
 
 /// Service for interacting with cursor-agent
 #[derive(Debug, Clone)]
-pub struct CursorAgent;
+pub struct CursorAgent {
+    binary: PathBuf,
+    subcommand: String,
+    print_flag: String,
+    save_raw_responses: bool,
+}
 
 impl CursorAgent {
-    pub fn new() -> Self {
-        Self
+    /// Use `GITAI_AGENT_BIN` if set, then `agent.binary` from config, then
+    /// `cursor-agent` from PATH
+    pub fn new(config: &AgentConfig) -> Self {
+        let binary = std::env::var_os(AGENT_BIN_ENV)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| config.binary.clone())
+            .unwrap_or_else(|| PathBuf::from("cursor-agent"));
+
+        Self {
+            binary,
+            subcommand: config
+                .subcommand
+                .clone()
+                .unwrap_or_else(|| DEFAULT_SUBCOMMAND.to_string()),
+            print_flag: config
+                .print_flag
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PRINT_FLAG.to_string()),
+            save_raw_responses: config.save_raw_responses.unwrap_or(false),
+        }
+    }
+
+    /// Arguments passing `prompt` to the agent, asking it to print its
+    /// response rather than act when `print` is set
+    fn prompt_args<'a>(&'a self, prompt: &'a str, print: bool) -> Vec<&'a str> {
+        let mut args = Vec::new();
+        if !self.subcommand.is_empty() {
+            args.push(self.subcommand.as_str());
+        }
+        args.push(prompt);
+        if print && !self.print_flag.is_empty() {
+            args.push(self.print_flag.as_str());
+        }
+        args
+    }
+
+    /// Executable this agent runs
+    pub fn binary(&self) -> &PathBuf {
+        &self.binary
     }
 
-    /// Execute cursor-agent with the given prompt
+    /// Version reported by `cursor-agent --version`, if it can be determined
+    pub fn version(&self) -> Option<String> {
+        let output = StdCommand::new(&self.binary)
+            .arg("--version")
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|version| !version.is_empty())
+    }
+
+    /// Execute cursor-agent with the given prompt. The agent may already have
+    /// changed the repository when it fails, so unlike [`Self::capture`] this
+    /// never retries without file contents, even for a prompt too long for the
+    /// model.
     pub async fn execute(&self, prompt: &str, no_confirm: bool) -> Result<()> {
-        let mut cmd = StdCommand::new("cursor-agent");
-        cmd.args(["prompt", prompt]);
+        self.execute_with_env(prompt, no_confirm, &[]).await
+    }
+
+    /// Execute cursor-agent with extra environment variables, which also
+    /// reach the git commands it runs
+    pub async fn execute_with_env(
+        &self,
+        prompt: &str,
+        no_confirm: bool,
+        env: &[(&str, &str)],
+    ) -> Result<()> {
+        let mut cmd = StdCommand::new(&self.binary);
+        cmd.args(self.prompt_args(prompt, false));
+        cmd.envs(env.iter().copied());
 
         if no_confirm {
             cmd.arg("--force");
         }
 
-        let status = cmd.status().context("Failed to run cursor-agent")?;
+        let status = cmd
+            .status()
+            .with_context(|| format!("Failed to run {}", self.binary.display()))?;
 
         if !status.success() {
             anyhow::bail!("cursor-agent command failed");
@@ -27,4 +154,129 @@ impl CursorAgent {
 
         Ok(())
     }
+
+    /// Run cursor-agent non-interactively and return what it printed. Nothing
+    /// is changed in print mode, so a prompt too long for the model is safely
+    /// retried once without file contents.
+    pub async fn capture(&self, prompt: &str) -> Result<String> {
+        match self.capture_once(prompt) {
+            Err(e) if e.is::<ContextLengthExceeded>() => {
+                let shorter = shorter_prompt(prompt).ok_or(e)?;
+                self.capture_once(&shorter)
+            }
+            result => result,
+        }
+    }
+
+    fn capture_once(&self, prompt: &str) -> Result<String> {
+        let output = StdCommand::new(&self.binary)
+            .args(self.prompt_args(prompt, true))
+            .output()
+            .with_context(|| format!("Failed to run {}", self.binary.display()))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if is_context_length_error(&stderr) {
+                return Err(ContextLengthExceeded.into());
+            }
+            anyhow::bail!("cursor-agent command failed: {}", stderr.trim());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Report a captured response that failed to parse, saving it to
+    /// `.git/git-ai/debug/<context>-<timestamp>.txt` when `agent.save_raw_responses`
+    /// is on. Returns `error` so callers can use this in `map_err`.
+    pub fn raw_response_error(
+        &self,
+        context: &str,
+        response: &str,
+        error: anyhow::Error,
+    ) -> anyhow::Error {
+        if !self.save_raw_responses {
+            eprintln!(
+                "{}Set `agent.save_raw_responses: true` to keep the raw cursor-agent response for debugging",
+                icon(Icon::Hint)
+            );
+            return error;
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let saved = git::git_path(&format!("git-ai/debug/{}-{}.txt", context, timestamp)).and_then(
+            |path| {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(&path, response)?;
+                Ok(path)
+            },
+        );
+
+        match saved {
+            Ok(path) => eprintln!(
+                "{}Raw cursor-agent response saved to {}",
+                icon(Icon::Saved),
+                path.display()
+            ),
+            Err(e) => eprintln!(
+                "{}Could not save the raw cursor-agent response: {}",
+                icon(Icon::Warn),
+                e
+            ),
+        }
+
+        error
+    }
+}
+
+/// Prompt to retry with after a context-length error, announcing the retry
+fn shorter_prompt(prompt: &str) -> Option<String> {
+    let shorter = context::omit_file_contents(prompt)?;
+    eprintln!(
+        "{}Prompt is too long for the model; retrying once without file contents",
+        icon(Icon::Warn)
+    );
+    Some(shorter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_context_length_error() {
+        assert!(is_context_length_error(
+            "Error: This model's maximum context length is 200000 tokens"
+        ));
+        assert!(is_context_length_error(
+            "warning: slow\nerror: Prompt is too long\n"
+        ));
+        assert!(!is_context_length_error("error: not authenticated"));
+        assert!(!is_context_length_error(
+            "+ // keep under the context window\nerror: not authenticated"
+        ));
+        assert!(!is_context_length_error("Reading the prompt is too long"));
+    }
+
+    #[test]
+    fn test_prompt_args() {
+        let agent = CursorAgent::new(&AgentConfig::default());
+        assert_eq!(
+            agent.prompt_args("hi", true),
+            vec!["prompt", "hi", "--print"]
+        );
+        assert_eq!(agent.prompt_args("hi", false), vec!["prompt", "hi"]);
+
+        let agent = CursorAgent::new(&AgentConfig {
+            subcommand: Some(String::new()),
+            print_flag: Some("-p".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(agent.prompt_args("hi", true), vec!["hi", "-p"]);
+    }
 }
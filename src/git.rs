@@ -0,0 +1,1197 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command as StdCommand, Stdio};
+use std::sync::OnceLock;
+
+/// git executable from the `git.binary` setting, set once at startup
+static GIT_BINARY: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `path` instead of `git` from PATH for every git invocation
+pub fn set_binary(path: PathBuf) {
+    let _ = GIT_BINARY.set(path);
+}
+
+fn binary() -> &'static Path {
+    GIT_BINARY
+        .get()
+        .map(PathBuf::as_path)
+        .unwrap_or(Path::new("git"))
+}
+
+fn command() -> StdCommand {
+    StdCommand::new(binary())
+}
+
+/// Explain a failure to start git, with installation guidance when it is missing
+fn spawn_error(e: std::io::Error) -> anyhow::Error {
+    if e.kind() == std::io::ErrorKind::NotFound {
+        anyhow::anyhow!(
+            "git executable not found ({}). Install git from https://git-scm.com/downloads \
+             or point the `git.binary` setting at it",
+            binary().display()
+        )
+    } else {
+        anyhow::Error::new(e).context("Failed to run git")
+    }
+}
+
+/// Check that git can be run, so a missing install is reported up front
+pub fn ensure_available() -> Result<()> {
+    command().arg("--version").output().map_err(spawn_error)?;
+    Ok(())
+}
+
+/// Run a git command and return its stdout with trailing whitespace removed
+pub fn run(args: &[&str]) -> Result<String> {
+    run_raw(args).map(|stdout| stdout.trim_end().to_string())
+}
+
+/// Run a git command and return its stdout untouched
+pub fn run_raw(args: &[&str]) -> Result<String> {
+    run_with_env(args, &[])
+}
+
+/// Run a git command with extra environment variables and return its stdout untouched
+pub fn run_with_env(args: &[&str], env: &[(&str, &str)]) -> Result<String> {
+    let output = command()
+        .args(args)
+        .envs(env.iter().copied())
+        .output()
+        .map_err(spawn_error)?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Run a git command with `input` on its stdin and return its stdout untouched
+pub fn run_with_input(args: &[&str], env: &[(&str, &str)], input: &str) -> Result<String> {
+    let mut child = command()
+        .args(args)
+        .envs(env.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(spawn_error)?;
+
+    child
+        .stdin
+        .take()
+        .context("Failed to open git stdin")?
+        .write_all(input.as_bytes())
+        .context("Failed to write to git")?;
+
+    let output = child.wait_with_output().context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Hash text as a git blob, giving a stable hex id without writing to the object store
+pub fn hash_text(text: &str) -> Result<String> {
+    run_with_input(&["hash-object", "--stdin"], &[], text).map(|id| id.trim().to_string())
+}
+
+/// Write an index file at `index` holding HEAD plus the staged versions of
+/// `paths` (relative to the repository root). A commit made with
+/// `GIT_INDEX_FILE` pointing at it contains only those staged changes, and
+/// leaves the rest of the real index staged.
+pub fn write_partial_index(index: &Path, paths: &[String]) -> Result<()> {
+    let index = index.to_string_lossy();
+    let env = [("GIT_INDEX_FILE", index.as_ref())];
+    if head_commit().is_some() {
+        run_with_env(&["read-tree", "HEAD"], &env)?;
+    } else {
+        run_with_env(&["read-tree", "--empty"], &env)?;
+    }
+
+    // `:(top,literal)` keeps the root-relative paths working from a subdirectory
+    let pathspecs: Vec<String> = paths
+        .iter()
+        .map(|path| format!(":(top,literal){}", path))
+        .collect();
+    let mut args = vec!["ls-files", "--stage", "--full-name", "-z", "--"];
+    args.extend(pathspecs.iter().map(String::as_str));
+    let staged = run_raw(&args)?;
+
+    run_with_input(
+        &["update-index", "-z", "--index-info"],
+        &env,
+        &index_info(&staged, paths),
+    )
+    .map(|_| ())
+}
+
+/// `git update-index -z --index-info` input copying the `git ls-files --stage -z`
+/// entries in `staged`, and removing each of `paths` without one (a staged deletion)
+fn index_info(staged: &str, paths: &[String]) -> String {
+    let entries: Vec<&str> = staged
+        .split('\0')
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    let mut info: Vec<String> = entries.iter().map(|entry| entry.to_string()).collect();
+
+    for path in paths {
+        let present = entries
+            .iter()
+            .any(|entry| entry.split_once('\t').map(|(_, p)| p) == Some(path.as_str()));
+        if !present {
+            info.push(format!("0 {}\t{}", "0".repeat(40), path));
+        }
+    }
+
+    info.iter().map(|entry| format!("{}\0", entry)).collect()
+}
+
+/// Check whether a revision resolves to a commit
+pub fn rev_exists(rev: &str) -> bool {
+    run(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{}^{{commit}}", rev),
+    ])
+    .is_ok()
+}
+
+/// Resolve HEAD to a commit sha, or `None` outside a repository or before the first commit
+pub fn head_commit() -> Option<String> {
+    run(&["rev-parse", "--verify", "--quiet", "HEAD"]).ok()
+}
+
+/// Check whether `ancestor` is reachable from `descendant` (a commit is its own ancestor)
+pub fn is_ancestor(ancestor: &str, descendant: &str) -> bool {
+    run(&["merge-base", "--is-ancestor", ancestor, descendant]).is_ok()
+}
+
+/// Full message of the commit `rev`
+pub fn commit_message(rev: &str) -> Result<String> {
+    run(&["show", "-s", "--format=%B", rev])
+}
+
+/// Parent shas of a commit (empty for a root commit)
+pub fn commit_parents(rev: &str) -> Result<Vec<String>> {
+    let output = run(&["rev-list", "--parents", "-n", "1", rev])?;
+    Ok(output
+        .split_whitespace()
+        .skip(1)
+        .map(str::to_string)
+        .collect())
+}
+
+/// Remote-tracking branches that already contain a commit
+pub fn remote_branches_containing(rev: &str) -> Result<Vec<String>> {
+    let output = run(&[
+        "branch",
+        "--remotes",
+        "--contains",
+        rev,
+        "--format=%(refname:short)",
+    ])?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Files that would conflict when merging `branch` into HEAD, computed with
+/// `git merge-tree` so neither the index nor the working tree is touched
+pub fn merge_conflicts(branch: &str) -> Result<Vec<String>> {
+    let output = command()
+        .args([
+            "merge-tree",
+            "--write-tree",
+            "--name-only",
+            "--no-messages",
+            "HEAD",
+            branch,
+        ])
+        .output()
+        .map_err(spawn_error)?;
+
+    // Exit status 1 means the merge has conflicts; anything else non-zero is an error
+    match output.status.code() {
+        Some(0) => Ok(Vec::new()),
+        Some(1) => Ok(parse_merge_tree_conflicts(&String::from_utf8_lossy(
+            &output.stdout,
+        ))),
+        _ => anyhow::bail!(
+            "git merge-tree failed (git 2.38 or newer is required): {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    }
+}
+
+/// Conflicted paths from `git merge-tree --name-only` output, which lists
+/// the resulting tree id first and then one path per line
+fn parse_merge_tree_conflicts(output: &str) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    for path in output.lines().skip(1).take_while(|line| !line.is_empty()) {
+        if !files.iter().any(|f| f == path) {
+            files.push(path.to_string());
+        }
+    }
+    files
+}
+
+/// Check whether HEAD points at a commit (false on a freshly initialized repo)
+pub fn has_commits() -> bool {
+    rev_exists("HEAD")
+}
+
+/// Name of the checked-out branch, or `None` on a detached HEAD
+pub fn current_branch() -> Option<String> {
+    run(&["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .ok()
+        .filter(|branch| !branch.is_empty())
+}
+
+/// Determine the branch a PR would most likely target
+pub fn default_base_branch() -> Option<String> {
+    // Prefer the remote's advertised default branch
+    if let Ok(head) = run(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]) {
+        if !head.is_empty() {
+            return Some(head);
+        }
+    }
+
+    ["main", "master", "origin/main", "origin/master"]
+        .iter()
+        .find(|candidate| rev_exists(candidate))
+        .map(|candidate| candidate.to_string())
+}
+
+/// Upstream tracking branch of the current branch, if one is configured
+pub fn upstream_branch() -> Option<String> {
+    run(&[
+        "rev-parse",
+        "--abbrev-ref",
+        "--symbolic-full-name",
+        "@{upstream}",
+    ])
+    .ok()
+    .filter(|upstream| !upstream.is_empty())
+}
+
+/// Size in bytes of the blob at `path` in `rev`, if it exists there
+pub fn blob_size(rev: &str, path: &str) -> Option<u64> {
+    run(&["cat-file", "-s", &format!("{}:{}", rev, path)])
+        .ok()
+        .and_then(|size| size.parse().ok())
+}
+
+/// Content of `path` as staged in the index (`git show :<path>`)
+pub fn staged_content(path: &str) -> Result<String> {
+    run_raw(&["show", &format!(":{}", path)])
+}
+
+/// Lines added by a diff, paired with the file they were added to
+pub fn added_lines(diff_args: &[&str]) -> Result<Vec<(String, String)>> {
+    let mut args = vec!["diff", "--unified=0", "--no-color", "--no-ext-diff"];
+    args.extend_from_slice(diff_args);
+    Ok(parse_added_lines(&run_raw(&args)?))
+}
+
+/// Lines still to come in the current hunk of a unified diff, counted down
+/// from its `@@ -a,b +c,d @@` header. Inside a hunk, a line such as `--- x`
+/// is a removed `-- x` rather than a file header.
+#[derive(Debug, Default)]
+struct Hunk {
+    old: usize,
+    new: usize,
+}
+
+impl Hunk {
+    /// Parse a hunk header; a missing count means one line
+    fn parse(line: &str) -> Option<Self> {
+        let ranges = line.strip_prefix("@@ -")?.split(" @@").next()?;
+        let (old, new) = ranges.split_once(" +")?;
+        let count = |range: &str| match range.split_once(',') {
+            Some((_, count)) => count.parse().ok(),
+            None => Some(1),
+        };
+        Some(Self {
+            old: count(old)?,
+            new: count(new)?,
+        })
+    }
+
+    fn is_done(&self) -> bool {
+        self.old == 0 && self.new == 0
+    }
+
+    /// Count off a line of the hunk body
+    fn consume(&mut self, line: &str) {
+        match line.chars().next() {
+            Some('-') => self.old = self.old.saturating_sub(1),
+            Some('+') => self.new = self.new.saturating_sub(1),
+            // "\ No newline at end of file" belongs to the previous line
+            Some('\\') => {}
+            _ => {
+                self.old = self.old.saturating_sub(1);
+                self.new = self.new.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// Extract added lines from unified diff output
+fn parse_added_lines(diff: &str) -> Vec<(String, String)> {
+    let mut current_file = String::new();
+    let mut hunk = Hunk::default();
+    let mut added = Vec::new();
+
+    for line in diff.lines() {
+        if !hunk.is_done() {
+            if let Some(content) = line.strip_prefix('+') {
+                added.push((current_file.clone(), content.to_string()));
+            }
+            hunk.consume(line);
+        } else if let Some(next) = Hunk::parse(line) {
+            hunk = next;
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            current_file = path.strip_prefix("b/").unwrap_or(path).to_string();
+        }
+    }
+
+    added
+}
+
+/// Authors (`Name <email>`) of the last `limit` non-merge commits reachable
+/// from `rev` that touched any of `paths`, newest first
+pub fn path_authors(rev: &str, paths: &[String], limit: usize) -> Result<Vec<String>> {
+    let limit = format!("--max-count={}", limit);
+    let mut args = vec![
+        "log",
+        "--no-merges",
+        "--format=%aN <%aE>",
+        &limit,
+        rev,
+        "--",
+    ];
+    args.extend(paths.iter().map(String::as_str));
+    let output = run(&args)?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Staged files matching any of the given pathspecs
+pub fn staged_files(pathspecs: &[String]) -> Result<Vec<String>> {
+    let mut args = vec!["diff", "--cached", "--name-only", "--"];
+    args.extend(pathspecs.iter().map(String::as_str));
+    let output = run(&args)?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// List files changed on the current branch since it diverged from `base`
+pub fn branch_changed_files(base: &str) -> Result<Vec<String>> {
+    let output = run(&["diff", "--name-only", &format!("{}...HEAD", base)])?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Push `branch` to `remote` and make it the branch's upstream
+pub fn push(remote: &str, branch: &str) -> Result<()> {
+    run(&["push", "--set-upstream", remote, branch]).map(|_| ())
+}
+
+/// Top-level directory of the working tree
+pub fn repo_root() -> Result<PathBuf> {
+    run(&["rev-parse", "--show-toplevel"]).map(PathBuf::from)
+}
+
+/// Resolve a path inside the repository's `.git` directory
+pub fn git_path(name: &str) -> Result<PathBuf> {
+    run(&["rev-parse", "--git-path", name]).map(PathBuf::from)
+}
+
+/// [`git_path`] made absolute, for handing to commands that run from another
+/// directory (git runs editors and hooks from the top of the worktree)
+pub fn absolute_git_path(name: &str) -> Result<PathBuf> {
+    let path = git_path(name)?;
+    if path.is_absolute() {
+        return Ok(path);
+    }
+    Ok(std::env::current_dir()
+        .context("Failed to read the current directory")?
+        .join(path))
+}
+
+/// Hooks that run during `git commit`
+const COMMIT_HOOKS: &[&str] = &[
+    "pre-commit",
+    "prepare-commit-msg",
+    "commit-msg",
+    "post-commit",
+];
+
+/// Names of the commit hooks installed in this repository (honors `core.hooksPath`)
+pub fn commit_hooks() -> Vec<&'static str> {
+    let Ok(hooks_dir) = git_path("hooks") else {
+        return Vec::new();
+    };
+
+    COMMIT_HOOKS
+        .iter()
+        .copied()
+        .filter(|hook| is_executable(&hooks_dir.join(hook)))
+        .collect()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Read all values of a (possibly multi-valued) git config key
+pub fn config_get_all(key: &str) -> Vec<String> {
+    // `git config` exits non-zero when the key is unset
+    run(&["config", "--get-all", key])
+        .map(|output| output.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Read the full messages of the last `count` commits on HEAD, newest first
+pub fn recent_commit_messages(count: usize) -> Result<Vec<String>> {
+    let output = run_raw(&["log", "-n", &count.to_string(), "--format=%B%x00"])?;
+    Ok(output
+        .split('\0')
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Count commits reachable from HEAD, optionally filtered by extra `rev-list` options
+pub fn commit_count(filters: &[&str]) -> Result<usize> {
+    let mut args = vec!["rev-list", "--count"];
+    args.extend_from_slice(filters);
+    args.push("HEAD");
+
+    let count = run(&args)?;
+    count
+        .parse()
+        .with_context(|| format!("Unexpected commit count from git: {}", count))
+}
+
+/// Lines inserted plus deleted in the changes a commit would pick up: the
+/// staged changes if there are any, otherwise the unstaged ones
+pub fn pending_change_lines() -> Result<usize> {
+    let args: &[&str] = if repository_status()?.has_staged() {
+        &["diff", "--numstat", "--cached"]
+    } else {
+        &["diff", "--numstat"]
+    };
+    Ok(parse_numstat_total(&run(args)?))
+}
+
+/// `git diff --stat` summary of the changes a commit would pick up: the
+/// staged changes if there are any, otherwise the unstaged ones
+pub fn pending_diff_stat() -> Result<String> {
+    if repository_status()?.has_staged() {
+        run(&["diff", "--stat", "--cached"])
+    } else {
+        run(&["diff", "--stat"])
+    }
+}
+
+/// `git diff --stat` summary of the branch's changes since it left `base`
+pub fn branch_diff_stat(base: &str) -> Result<String> {
+    run(&["diff", "--stat", &format!("{}...HEAD", base)])
+}
+
+/// Lines added by the changes a commit would pick up: the staged changes if
+/// there are any, otherwise the unstaged ones
+pub fn pending_added_lines() -> Result<Vec<(String, String)>> {
+    if repository_status()?.has_staged() {
+        added_lines(&["--cached"])
+    } else {
+        added_lines(&[])
+    }
+}
+
+/// Files in the changes a commit would pick up (the staged changes if there
+/// are any, otherwise the unstaged ones), each paired with whether git treats
+/// it as binary
+pub fn pending_files() -> Result<Vec<(String, bool)>> {
+    let args: &[&str] = if repository_status()?.has_staged() {
+        &["diff", "--numstat", "-z", "--no-renames", "--cached"]
+    } else {
+        &["diff", "--numstat", "-z", "--no-renames"]
+    };
+    Ok(parse_numstat_files(&run_raw(args)?))
+}
+
+/// Parse `git diff --numstat -z --no-renames` into paths and binary flags.
+/// Binary files report `-` instead of line counts.
+fn parse_numstat_files(numstat: &str) -> Vec<(String, bool)> {
+    numstat
+        .split('\0')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(3, '\t');
+            let (added, _, path) = (fields.next()?, fields.next()?, fields.next()?);
+            Some((path.to_string(), added == "-"))
+        })
+        .collect()
+}
+
+/// Which of `paths` are stored with Git LFS (`filter=lfs` in .gitattributes)
+pub fn lfs_tracked(paths: &[&str]) -> Result<Vec<String>> {
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut args = vec!["check-attr", "-z", "filter", "--"];
+    args.extend_from_slice(paths);
+    Ok(parse_lfs_attrs(&run_raw(&args)?))
+}
+
+/// Parse `git check-attr -z filter` output (`path NUL attr NUL value NUL`
+/// triples) into the paths whose filter is `lfs`
+fn parse_lfs_attrs(output: &str) -> Vec<String> {
+    let fields: Vec<&str> = output.split('\0').collect();
+    fields
+        .chunks(3)
+        .filter(|triple| triple.len() == 3 && triple[2] == "lfs")
+        .map(|triple| triple[0].to_string())
+        .collect()
+}
+
+/// Sum insertions and deletions from `git diff --numstat`, ignoring binary files
+fn parse_numstat_total(numstat: &str) -> usize {
+    numstat
+        .lines()
+        .flat_map(|line| line.split('\t').take(2))
+        .filter_map(|count| count.parse::<usize>().ok())
+        .sum()
+}
+
+/// One commit as shown in the post-commit summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSummary {
+    pub sha: String,
+    pub subject: String,
+    pub files: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+/// Summarize the commits in `range` (any `git log` revision range), oldest first
+pub fn commit_summaries(range: &str) -> Result<Vec<CommitSummary>> {
+    let output = run_raw(&[
+        "log",
+        "--reverse",
+        "--format=%x00%h%x09%s",
+        "--shortstat",
+        range,
+    ])?;
+    Ok(parse_commit_summaries(&output))
+}
+
+/// Parse `git log --format=%x00%h%x09%s --shortstat` output
+fn parse_commit_summaries(output: &str) -> Vec<CommitSummary> {
+    output
+        .split('\0')
+        .filter_map(|entry| {
+            let mut lines = entry.lines();
+            let (sha, subject) = lines.next()?.split_once('\t')?;
+            let mut summary = CommitSummary {
+                sha: sha.to_string(),
+                subject: subject.to_string(),
+                files: 0,
+                insertions: 0,
+                deletions: 0,
+            };
+
+            // e.g. " 2 files changed, 3 insertions(+), 1 deletion(-)"
+            for part in lines.flat_map(|line| line.split(',')) {
+                let mut words = part.split_whitespace();
+                let (Some(count), Some(kind)) = (words.next(), words.next()) else {
+                    continue;
+                };
+                let Ok(count) = count.parse() else {
+                    continue;
+                };
+                if kind.starts_with("file") {
+                    summary.files = count;
+                } else if kind.starts_with("insertion") {
+                    summary.insertions = count;
+                } else if kind.starts_with("deletion") {
+                    summary.deletions = count;
+                }
+            }
+
+            Some(summary)
+        })
+        .collect()
+}
+
+/// Status of a single path as reported by `git status --porcelain`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStatus {
+    pub path: String,
+    /// Original path for renamed (`R`) or copied (`C`) entries
+    pub orig_path: Option<String>,
+    /// Status in the index (staged), e.g. `M`, `A`, `D`, or ` ` when unchanged
+    pub index: char,
+    /// Status in the working tree (unstaged), e.g. `M`, `D`, or ` ` when unchanged
+    pub worktree: char,
+}
+
+impl FileStatus {
+    pub fn is_untracked(&self) -> bool {
+        self.index == '?' && self.worktree == '?'
+    }
+
+    pub fn is_staged(&self) -> bool {
+        !self.is_untracked() && self.index != ' '
+    }
+
+    /// Staged, then edited again: the index and working tree hold different changes
+    pub fn is_partially_staged(&self) -> bool {
+        self.is_staged() && self.is_unstaged() && !self.is_conflicted()
+    }
+
+    pub fn is_unstaged(&self) -> bool {
+        !self.is_untracked() && self.worktree != ' '
+    }
+
+    /// Unmerged path left by a merge, rebase, or cherry-pick conflict
+    pub fn is_conflicted(&self) -> bool {
+        matches!(
+            (self.index, self.worktree),
+            ('D', 'D')
+                | ('A', 'U')
+                | ('U', 'D')
+                | ('U', 'A')
+                | ('D', 'U')
+                | ('A', 'A')
+                | ('U', 'U')
+        )
+    }
+}
+
+/// Snapshot of the working tree and index
+#[derive(Debug, Clone, Default)]
+pub struct RepositoryStatus {
+    pub files: Vec<FileStatus>,
+}
+
+impl RepositoryStatus {
+    pub fn has_staged(&self) -> bool {
+        self.files.iter().any(FileStatus::is_staged)
+    }
+
+    pub fn has_unstaged(&self) -> bool {
+        self.files.iter().any(FileStatus::is_unstaged)
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        self.files.iter().any(FileStatus::is_conflicted)
+    }
+
+    pub fn conflicted(&self) -> Vec<&FileStatus> {
+        self.files.iter().filter(|f| f.is_conflicted()).collect()
+    }
+
+    pub fn partially_staged(&self) -> Vec<&FileStatus> {
+        self.files
+            .iter()
+            .filter(|f| f.is_partially_staged())
+            .collect()
+    }
+
+    pub fn untracked(&self) -> Vec<&FileStatus> {
+        self.files.iter().filter(|f| f.is_untracked()).collect()
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Read the current repository status
+pub fn repository_status() -> Result<RepositoryStatus> {
+    let output = run_raw(&["status", "--porcelain", "-z"])?;
+    Ok(parse_porcelain(&output))
+}
+
+/// Parse `git status --porcelain -z` (v1) output.
+///
+/// Entries are NUL-terminated `XY path` records. Renames and copies are
+/// followed by an extra NUL-terminated field holding the original path.
+/// Malformed records are skipped rather than treated as errors.
+fn parse_porcelain(output: &str) -> RepositoryStatus {
+    let mut files = Vec::new();
+    let mut fields = output.split('\0');
+
+    while let Some(entry) = fields.next() {
+        let mut chars = entry.chars();
+        let (Some(index), Some(worktree), Some(' ')) = (chars.next(), chars.next(), chars.next())
+        else {
+            continue;
+        };
+
+        let path = chars.as_str();
+        if path.is_empty() {
+            continue;
+        }
+
+        let orig_path = if matches!(index, 'R' | 'C') || matches!(worktree, 'R' | 'C') {
+            match fields.next() {
+                Some(orig) if !orig.is_empty() => Some(orig.to_string()),
+                _ => continue,
+            }
+        } else {
+            None
+        };
+
+        files.push(FileStatus {
+            path: path.to_string(),
+            orig_path,
+            index,
+            worktree,
+        });
+    }
+
+    RepositoryStatus { files }
+}
+
+/// Files touched by a unified diff (e.g. a `git format-patch` file), with
+/// their change recorded in `index` as `A`, `D`, `R`, `C` or `M`
+pub fn parse_patch_files(patch: &str) -> Vec<FileStatus> {
+    let mut files: Vec<FileStatus> = Vec::new();
+    // The `diff --git` entry being read; its headers refine the paths and status
+    let mut entry: Option<(Option<String>, Option<String>, char)> = None;
+    let mut old_path: Option<String> = None;
+    let mut hunk = Hunk::default();
+
+    for line in patch.lines() {
+        if !hunk.is_done() {
+            hunk.consume(line);
+        } else if let Some(next) = Hunk::parse(line) {
+            hunk = next;
+        } else if let Some(paths) = line.strip_prefix("diff --git ") {
+            push_patch_file(&mut files, entry.take());
+            let (old, new) = diff_git_paths(paths);
+            entry = Some((Some(old), Some(new), 'M'));
+        } else if line.starts_with("diff ") {
+            push_patch_file(&mut files, entry.take());
+            old_path = None;
+        } else if let Some((old, new, status)) = entry.as_mut() {
+            // Extended headers cover renames, mode changes, and binary files,
+            // which have no `---`/`+++` lines
+            if line.starts_with("new file mode ") {
+                *status = 'A';
+            } else if line.starts_with("deleted file mode ") {
+                *status = 'D';
+            } else if let Some(path) = line.strip_prefix("rename from ") {
+                (*old, *status) = (Some(path.to_string()), 'R');
+            } else if let Some(path) = line.strip_prefix("rename to ") {
+                (*new, *status) = (Some(path.to_string()), 'R');
+            } else if let Some(path) = line.strip_prefix("copy from ") {
+                (*old, *status) = (Some(path.to_string()), 'C');
+            } else if let Some(path) = line.strip_prefix("copy to ") {
+                (*new, *status) = (Some(path.to_string()), 'C');
+            } else if let Some(path) = line.strip_prefix("--- ") {
+                if let Some(path) = patch_path(path, "a/") {
+                    *old = Some(path);
+                }
+            } else if let Some(path) = line.strip_prefix("+++ ") {
+                if let Some(path) = patch_path(path, "b/") {
+                    *new = Some(path);
+                }
+            }
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            old_path = patch_path(path, "a/");
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            // A plain unified diff, identified only by its headers
+            let new_path = patch_path(path, "b/");
+            let status = match (&old_path, &new_path) {
+                (None, Some(_)) => 'A',
+                (Some(_), None) => 'D',
+                (Some(old), Some(new)) if old != new => 'R',
+                _ => 'M',
+            };
+            push_patch_file(&mut files, Some((old_path.take(), new_path, status)));
+        }
+    }
+    push_patch_file(&mut files, entry);
+
+    files
+}
+
+/// Add a file read from a patch, unless it is already listed
+fn push_patch_file(
+    files: &mut Vec<FileStatus>,
+    entry: Option<(Option<String>, Option<String>, char)>,
+) {
+    let Some((old, new, status)) = entry else {
+        return;
+    };
+    let (path, orig_path) = match (old, new) {
+        (Some(old), None) => (old, None),
+        (None, Some(new)) => (new, None),
+        (Some(old), Some(new)) if matches!(status, 'R' | 'C') && old != new => (new, Some(old)),
+        (_, Some(new)) => (new, None),
+        (None, None) => return,
+    };
+
+    if !files.iter().any(|f| f.path == path) {
+        files.push(FileStatus {
+            path,
+            orig_path,
+            index: status,
+            worktree: ' ',
+        });
+    }
+}
+
+/// Old and new paths from the `a/<old> b/<new>` of a `diff --git` line. When
+/// both are the same, the line is split in the middle, so paths containing
+/// ` b/` are still read correctly.
+fn diff_git_paths(paths: &str) -> (String, String) {
+    let half = paths.len().saturating_sub(1) / 2;
+    let split = if paths.len() % 2 == 1
+        && paths.is_char_boundary(half)
+        && paths[..half].strip_prefix("a/") == paths[half + 1..].strip_prefix("b/")
+    {
+        Some(half)
+    } else {
+        paths.find(" b/")
+    };
+
+    match split {
+        Some(split) => {
+            let (old, new) = (&paths[..split], &paths[split + 1..]);
+            (
+                old.strip_prefix("a/").unwrap_or(old).to_string(),
+                new.strip_prefix("b/").unwrap_or(new).to_string(),
+            )
+        }
+        None => (paths.to_string(), paths.to_string()),
+    }
+}
+
+/// Path from a `---`/`+++` header, `None` for `/dev/null`
+fn patch_path(header: &str, prefix: &str) -> Option<String> {
+    // Headers may carry a tab-separated timestamp after the path
+    let path = header.split('\t').next().unwrap_or(header).trim_end();
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.strip_prefix(prefix).unwrap_or(path).to_string())
+}
+
+/// Stage the given paths
+pub fn add(paths: &[&str]) -> Result<()> {
+    let mut args = vec!["add", "--"];
+    args.extend_from_slice(paths);
+    run(&args).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain() {
+        let status = parse_porcelain("M  src/main.rs\0 M README.md\0?? notes.txt\0");
+        assert_eq!(status.files.len(), 3);
+        assert!(status.files[0].is_staged());
+        assert!(!status.files[0].is_unstaged());
+        assert!(status.files[1].is_unstaged());
+        assert!(status.files[2].is_untracked());
+        assert_eq!(status.untracked()[0].path, "notes.txt");
+    }
+
+    #[test]
+    fn test_parse_commit_summaries() {
+        let output = "\0abc1234\tfeat(api): add login\n\n 2 files changed, 30 insertions(+), 1 deletion(-)\n\
+                      \0def5678\tdocs: tweak readme\n\n 1 file changed, 2 deletions(-)\n\
+                      \0fedcba9\tchore: empty\n";
+        assert_eq!(
+            parse_commit_summaries(output),
+            vec![
+                CommitSummary {
+                    sha: "abc1234".to_string(),
+                    subject: "feat(api): add login".to_string(),
+                    files: 2,
+                    insertions: 30,
+                    deletions: 1,
+                },
+                CommitSummary {
+                    sha: "def5678".to_string(),
+                    subject: "docs: tweak readme".to_string(),
+                    files: 1,
+                    insertions: 0,
+                    deletions: 2,
+                },
+                CommitSummary {
+                    sha: "fedcba9".to_string(),
+                    subject: "chore: empty".to_string(),
+                    files: 0,
+                    insertions: 0,
+                    deletions: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conflicted_status() {
+        let status = parse_porcelain("UU src/lib.rs\0AA new.rs\0M  clean.rs\0DU gone.rs\0");
+        assert!(status.has_conflicts());
+        let conflicted: Vec<&str> = status
+            .conflicted()
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(conflicted, vec!["src/lib.rs", "new.rs", "gone.rs"]);
+        assert!(!parse_porcelain("M  clean.rs\0").has_conflicts());
+    }
+
+    #[test]
+    fn test_partially_staged_status() {
+        let status =
+            parse_porcelain("MM src/lib.rs\0AM new.rs\0M  clean.rs\0 M dirty.rs\0UU conflict.rs\0");
+        let partial: Vec<&str> = status
+            .partially_staged()
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(partial, vec!["src/lib.rs", "new.rs"]);
+    }
+
+    #[test]
+    fn test_only_untracked_status() {
+        let status = parse_porcelain("?? new_file.rs\0?? docs/\0");
+        assert!(!status.has_staged());
+        assert!(!status.has_unstaged());
+        assert_eq!(status.untracked().len(), 2);
+        assert!(parse_porcelain("").is_clean());
+    }
+
+    #[test]
+    fn test_hash_text_matches_git_blob_id() {
+        assert_eq!(
+            hash_text("hello\n").unwrap(),
+            "ce013625030ba8dba906f756967f9e9ca394464a"
+        );
+    }
+
+    #[test]
+    fn test_parse_numstat_files() {
+        assert_eq!(
+            parse_numstat_files("1\t1\tREADME.md\0-\t-\tdump.bin\0"),
+            vec![
+                ("README.md".to_string(), false),
+                ("dump.bin".to_string(), true)
+            ]
+        );
+        assert!(parse_numstat_files("").is_empty());
+    }
+
+    #[test]
+    fn test_index_info() {
+        let sha = "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391";
+        let staged = format!("100644 {} 0\tsrc/lib.rs\0", sha);
+        let paths = vec!["src/lib.rs".to_string(), "old.txt".to_string()];
+        assert_eq!(
+            index_info(&staged, &paths),
+            format!(
+                "100644 {} 0\tsrc/lib.rs\00 {}\told.txt\0",
+                sha,
+                "0".repeat(40)
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_lfs_attrs() {
+        assert_eq!(
+            parse_lfs_attrs("model.bin\0filter\0lfs\0logo.png\0filter\0unspecified\0"),
+            vec!["model.bin".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_numstat_total() {
+        assert_eq!(
+            parse_numstat_total("1\t1\tREADME.md\n-\t-\tlogo.png\n10\t0\tsrc/lib.rs\n"),
+            12
+        );
+        assert_eq!(parse_numstat_total(""), 0);
+    }
+
+    #[test]
+    fn test_parse_merge_tree_conflicts() {
+        let output =
+            "4b825dc642cb6eb9a060e54bf8d69288fbee4904\nsrc/lib.rs\nsrc/lib.rs\nREADME.md\n";
+        assert_eq!(
+            parse_merge_tree_conflicts(output),
+            vec!["src/lib.rs".to_string(), "README.md".to_string()]
+        );
+        assert!(
+            parse_merge_tree_conflicts("4b825dc642cb6eb9a060e54bf8d69288fbee4904\n").is_empty()
+        );
+    }
+
+    #[test]
+    fn test_parse_added_lines() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n\
+                    --- a/src/lib.rs\n\
+                    +++ b/src/lib.rs\n\
+                    @@ -1 +1 @@\n\
+                    -pub fn removed() {}\n\
+                    +pub fn added() {}\n\
+                    --- /dev/null\n\
+                    +++ b/new.txt\n\
+                    @@ -0,0 +1 @@\n\
+                    +hello\n";
+        assert_eq!(
+            parse_added_lines(diff),
+            vec![
+                ("src/lib.rs".to_string(), "pub fn added() {}".to_string()),
+                ("new.txt".to_string(), "hello".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_that_look_like_headers() {
+        let diff = "diff --git a/notes.md b/notes.md\n\
+                    --- a/notes.md\n\
+                    +++ b/notes.md\n\
+                    @@ -1,2 +1,2 @@\n\
+                    --- old rule\n\
+                    +++ new rule\n\
+                    \x20unchanged\n";
+        assert_eq!(
+            parse_added_lines(diff),
+            vec![("notes.md".to_string(), "++ new rule".to_string())]
+        );
+
+        let files = parse_patch_files(diff);
+        assert_eq!(files.len(), 1);
+        assert_eq!((files[0].path.as_str(), files[0].index), ("notes.md", 'M'));
+    }
+
+    #[test]
+    fn test_parse_patch_files_without_headers() {
+        let patch = "diff --git a/src/old name.rs b/src/new name.rs\n\
+                     similarity index 100%\n\
+                     rename from src/old name.rs\n\
+                     rename to src/new name.rs\n\
+                     diff --git a/logo.png b/logo.png\n\
+                     index 1234567..89abcde 100644\n\
+                     Binary files a/logo.png and b/logo.png differ\n\
+                     diff --git a/run.sh b/run.sh\n\
+                     old mode 100644\n\
+                     new mode 100755\n\
+                     diff --git a/icon.png b/icon.png\n\
+                     new file mode 100644\n\
+                     index 0000000..89abcde\n\
+                     Binary files /dev/null and b/icon.png differ\n";
+        let files = parse_patch_files(patch);
+        let summary: Vec<(&str, char)> = files.iter().map(|f| (f.path.as_str(), f.index)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/new name.rs", 'R'),
+                ("logo.png", 'M'),
+                ("run.sh", 'M'),
+                ("icon.png", 'A')
+            ]
+        );
+        assert_eq!(files[0].orig_path.as_deref(), Some("src/old name.rs"));
+        assert_eq!(
+            diff_git_paths("a/x b/y b/x b/y"),
+            ("x b/y".to_string(), "x b/y".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_porcelain_renames_and_copies() {
+        let status = parse_porcelain(
+            "R  src/new name.rs\0src/old -> name.rs\0C  copy.rs\0orig.rs\0M  lib.rs\0",
+        );
+        assert_eq!(status.files.len(), 3);
+        assert_eq!(status.files[0].path, "src/new name.rs");
+        assert_eq!(
+            status.files[0].orig_path.as_deref(),
+            Some("src/old -> name.rs")
+        );
+        assert_eq!(status.files[1].orig_path.as_deref(), Some("orig.rs"));
+        assert_eq!(status.files[2].path, "lib.rs");
+        assert_eq!(status.files[2].orig_path, None);
+    }
+
+    #[test]
+    fn test_parse_patch_files() {
+        let patch = "From abc Mon Sep 17 00:00:00 2001\n\
+                     Subject: [PATCH] add login\n\
+                     ---\n\
+                     diff --git a/src/auth.rs b/src/auth.rs\n\
+                     --- a/src/auth.rs\n\
+                     +++ b/src/auth.rs\n\
+                     @@ -1 +1 @@\n\
+                     -old\n\
+                     +new\n\
+                     diff --git a/tests/login.rs b/tests/login.rs\n\
+                     new file mode 100644\n\
+                     --- /dev/null\n\
+                     +++ b/tests/login.rs\n\
+                     diff --git a/old.rs b/old.rs\n\
+                     deleted file mode 100644\n\
+                     --- a/old.rs\n\
+                     +++ /dev/null\n";
+        let files = parse_patch_files(patch);
+        let summary: Vec<(&str, char)> = files.iter().map(|f| (f.path.as_str(), f.index)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/auth.rs", 'M'),
+                ("tests/login.rs", 'A'),
+                ("old.rs", 'D')
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_porcelain_skips_malformed_entries() {
+        for input in [
+            "M",
+            "MM",
+            "MMx",
+            "M  \0",
+            "R  renamed.rs",
+            "R  renamed.rs\0\0",
+            "é",
+            "\0\0\0",
+        ] {
+            let status = parse_porcelain(input);
+            assert!(status.is_clean(), "expected no entries for {:?}", input);
+        }
+
+        let status = parse_porcelain("garbage\0A  added.rs\0");
+        assert_eq!(status.files.len(), 1);
+        assert_eq!(status.files[0].path, "added.rs");
+    }
+}
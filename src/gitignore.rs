@@ -0,0 +1,280 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Built-in ignore patterns: lookup name, section name, and patterns
+const TEMPLATES: &[(&str, &str, &[&str])] = &[
+    (
+        "python",
+        "Python",
+        &[
+            "__pycache__/",
+            "*.py[cod]",
+            ".venv/",
+            "venv/",
+            ".env",
+            "*.egg-info/",
+            ".pytest_cache/",
+            ".mypy_cache/",
+            ".ruff_cache/",
+        ],
+    ),
+    (
+        "node",
+        "Node",
+        &[
+            "node_modules/",
+            "npm-debug.log*",
+            "yarn-debug.log*",
+            "yarn-error.log*",
+            "pnpm-debug.log*",
+            ".npm/",
+            "dist/",
+            "coverage/",
+        ],
+    ),
+    ("rust", "Rust", &["target/", "**/*.rs.bk", "*.pdb"]),
+    (
+        "go",
+        "Go",
+        &["*.exe", "*.test", "*.out", "vendor/", "go.work"],
+    ),
+    (
+        "java",
+        "Java",
+        &["*.class", "*.jar", "*.war", "target/", "build/", ".gradle/"],
+    ),
+    (
+        "macos",
+        "macOS",
+        &[".DS_Store", ".AppleDouble", ".LSOverride", "._*"],
+    ),
+    (
+        "windows",
+        "Windows",
+        &["Thumbs.db", "ehthumbs.db", "Desktop.ini", "$RECYCLE.BIN/"],
+    ),
+    (
+        "vscode",
+        "VSCode",
+        &[
+            ".vscode/*",
+            "!.vscode/settings.json",
+            "!.vscode/extensions.json",
+        ],
+    ),
+    ("jetbrains", "JetBrains", &[".idea/", "*.iml", "out/"]),
+];
+
+/// Section name and patterns of the built-in template for `name`, if any
+pub fn template(name: &str) -> Option<(&'static str, &'static [&'static str])> {
+    let name = name.trim();
+    TEMPLATES
+        .iter()
+        .find(|(key, section, _)| {
+            key.eq_ignore_ascii_case(name) || section.eq_ignore_ascii_case(name)
+        })
+        .map(|(_, section, patterns)| (*section, *patterns))
+}
+
+/// Name of the section a `# === <Name> ===` line opens
+fn section_start(line: &str) -> Option<&str> {
+    let name = line
+        .trim()
+        .strip_prefix("# === ")?
+        .strip_suffix(" ===")?
+        .trim();
+    (!name.is_empty() && !name.starts_with("End ")).then_some(name)
+}
+
+/// Whether `line` is the `# === End <name> ===` marker closing `name`
+fn is_section_end(line: &str, name: &str) -> bool {
+    line.trim()
+        .strip_prefix("# === End ")
+        .and_then(|rest| rest.strip_suffix(" ==="))
+        .is_some_and(|end| end.trim().eq_ignore_ascii_case(name))
+}
+
+/// A `.gitignore` file organized in marker-delimited sections:
+///
+/// ```text
+/// # === Python ===
+/// __pycache__/
+/// # === End Python ===
+/// ```
+///
+/// Lines outside sections are kept as they are. Section names are matched
+/// case-insensitively.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitignoreManager {
+    lines: Vec<String>,
+}
+
+impl GitignoreManager {
+    pub fn parse(content: &str) -> Self {
+        Self {
+            lines: content.lines().map(str::to_string).collect(),
+        }
+    }
+
+    /// Read a `.gitignore` file; a missing file is treated as empty
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_string())
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Names of the complete sections, in file order
+    pub fn list_sections(&self) -> Vec<String> {
+        let mut sections = Vec::new();
+        let mut index = 0;
+        while index < self.lines.len() {
+            match self.section_at(index) {
+                Some((name, end)) => {
+                    sections.push(name.to_string());
+                    index = end + 1;
+                }
+                None => index += 1,
+            }
+        }
+        sections
+    }
+
+    pub fn has_section(&self, name: &str) -> bool {
+        self.find_section(name).is_some()
+    }
+
+    /// Append a section with `patterns`. Returns `false`, changing nothing, if
+    /// the section already exists.
+    pub fn add_section(&mut self, name: &str, patterns: &[&str]) -> bool {
+        if self.has_section(name) {
+            return false;
+        }
+
+        while self.lines.last().is_some_and(|line| line.trim().is_empty()) {
+            self.lines.pop();
+        }
+        if !self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+
+        self.lines.push(format!("# === {} ===", name));
+        self.lines
+            .extend(patterns.iter().map(|pattern| pattern.to_string()));
+        self.lines.push(format!("# === End {} ===", name));
+        true
+    }
+
+    /// Delete a section, markers included, along with the blank line that
+    /// separated it from the rest of the file. Returns `false` if there is no
+    /// such section.
+    pub fn remove_section(&mut self, name: &str) -> bool {
+        let Some((mut start, mut end)) = self.find_section(name) else {
+            return false;
+        };
+
+        if start > 0 && self.lines[start - 1].trim().is_empty() {
+            start -= 1;
+        } else if self
+            .lines
+            .get(end + 1)
+            .is_some_and(|line| line.trim().is_empty())
+        {
+            end += 1;
+        }
+
+        self.lines.drain(start..=end);
+        true
+    }
+
+    /// First and last line of the section called `name`
+    fn find_section(&self, name: &str) -> Option<(usize, usize)> {
+        (0..self.lines.len()).find_map(|index| match self.section_at(index) {
+            Some((section, end)) if section.eq_ignore_ascii_case(name.trim()) => Some((index, end)),
+            _ => None,
+        })
+    }
+
+    /// Name and end line of a complete section starting at `index`
+    fn section_at(&self, index: usize) -> Option<(&str, usize)> {
+        let name = section_start(&self.lines[index])?;
+        let end =
+            (index + 1..self.lines.len()).find(|&end| is_section_end(&self.lines[end], name))?;
+        Some((name, end))
+    }
+}
+
+impl std::fmt::Display for GitignoreManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXISTING: &str = "# Local files\n.env.local\n\n# === Python ===\n__pycache__/\n# === End Python ===\n\ncustom.log\n";
+
+    #[test]
+    fn test_list_sections() {
+        let manager = GitignoreManager::parse(EXISTING);
+        assert_eq!(manager.list_sections(), vec!["Python".to_string()]);
+        assert!(manager.has_section("python"));
+
+        // An unterminated section is not a section
+        assert!(GitignoreManager::parse("# === Rust ===\ntarget/\n")
+            .list_sections()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_add_section_is_idempotent() {
+        let mut manager = GitignoreManager::parse(EXISTING);
+        assert!(manager.add_section("Rust", &["target/"]));
+        let once = manager.to_string();
+        assert!(once.ends_with("custom.log\n\n# === Rust ===\ntarget/\n# === End Rust ===\n"));
+
+        assert!(!manager.add_section("rust", &["target/"]));
+        assert!(!manager.add_section("Python", &["*.pyc"]));
+        assert_eq!(manager.to_string(), once);
+
+        let mut empty = GitignoreManager::default();
+        assert!(empty.add_section("Go", &["*.exe"]));
+        assert_eq!(empty.to_string(), "# === Go ===\n*.exe\n# === End Go ===\n");
+    }
+
+    #[test]
+    fn test_remove_section_preserves_surrounding_content() {
+        let mut manager = GitignoreManager::parse(EXISTING);
+        assert!(manager.remove_section("PYTHON"));
+        assert_eq!(
+            manager.to_string(),
+            "# Local files\n.env.local\n\ncustom.log\n"
+        );
+        assert!(!manager.remove_section("Python"));
+
+        let mut manager = GitignoreManager::parse(EXISTING);
+        manager.add_section("Rust", &["target/"]);
+        manager.remove_section("Rust");
+        assert_eq!(manager.to_string(), EXISTING);
+    }
+
+    #[test]
+    fn test_template() {
+        let (section, patterns) = template("Python").unwrap();
+        assert_eq!(section, "Python");
+        assert!(patterns.contains(&"__pycache__/"));
+        assert_eq!(template("macos").unwrap().0, "macOS");
+        assert!(template("cobol").is_none());
+    }
+}
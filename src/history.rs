@@ -0,0 +1,169 @@
+use crate::git;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A file an action changed, with its content before and after (`None` if it
+/// did not exist)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path relative to the repository root
+    pub path: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// A git-ai run that moved HEAD or changed files, and can be reverted with `git ai undo`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Action {
+    /// Command that performed the action (e.g. `commit`)
+    pub command: String,
+    /// HEAD before the command ran, `None` if the branch had no commits
+    pub head_before: Option<String>,
+    /// HEAD after the command ran
+    pub head_after: String,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Files changed outside of commits (e.g. `.gitignore` by `ignore add`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<FileChange>,
+}
+
+/// Content of the files at `paths` (relative to the repository root), taken
+/// before a command runs so its changes can be recorded
+pub fn snapshot(paths: &[&str]) -> Vec<(String, Option<String>)> {
+    let root = git::repo_root().unwrap_or_else(|_| PathBuf::from("."));
+    paths
+        .iter()
+        .map(|path| {
+            let content = std::fs::read_to_string(root.join(path)).ok();
+            (path.to_string(), content)
+        })
+        .collect()
+}
+
+/// Files whose content differs between the `before` and `after` snapshots
+fn changed_files(
+    before: Vec<(String, Option<String>)>,
+    after: Vec<(String, Option<String>)>,
+) -> Vec<FileChange> {
+    before
+        .into_iter()
+        .zip(after)
+        .filter(|((_, before), (_, after))| before != after)
+        .map(|((path, before), (_, after))| FileChange {
+            path,
+            before,
+            after,
+        })
+        .collect()
+}
+
+/// Actions recorded in `.git/git-ai/history.json`, oldest first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    pub actions: Vec<Action>,
+}
+
+impl History {
+    /// Path of the history file for the current repository
+    fn path() -> Result<PathBuf> {
+        git::git_path("git-ai/history.json")
+    }
+
+    /// Load the history, returning an empty one if nothing was recorded yet
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read history: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse history: {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize history")?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write history: {}", path.display()))
+    }
+
+    /// Record an action if HEAD moved or any of the `files_before` snapshot
+    /// changed while `command` ran. Nothing is recorded on an unborn branch.
+    pub fn record_if_changed(
+        command: &str,
+        head_before: Option<String>,
+        files_before: Vec<(String, Option<String>)>,
+    ) -> Result<()> {
+        let Some(head_after) = git::head_commit() else {
+            return Ok(());
+        };
+        let paths: Vec<&str> = files_before.iter().map(|(path, _)| path.as_str()).collect();
+        let files_after = snapshot(&paths);
+        let files = changed_files(files_before, files_after);
+        if head_before.as_deref() == Some(head_after.as_str()) && files.is_empty() {
+            return Ok(());
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let mut history = Self::load()?;
+        history.actions.push(Action {
+            command: command.to_string(),
+            head_before,
+            head_after,
+            timestamp,
+            files,
+        });
+        history.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_files() {
+        let before = vec![
+            (".gitignore".to_string(), Some("target/\n".to_string())),
+            ("kept".to_string(), Some("same".to_string())),
+            ("new".to_string(), None),
+        ];
+        let after = vec![
+            (
+                ".gitignore".to_string(),
+                Some("target/\n*.log\n".to_string()),
+            ),
+            ("kept".to_string(), Some("same".to_string())),
+            ("new".to_string(), Some("created".to_string())),
+        ];
+        assert_eq!(
+            changed_files(before, after),
+            vec![
+                FileChange {
+                    path: ".gitignore".to_string(),
+                    before: Some("target/\n".to_string()),
+                    after: Some("target/\n*.log\n".to_string()),
+                },
+                FileChange {
+                    path: "new".to_string(),
+                    before: None,
+                    after: Some("created".to_string()),
+                },
+            ]
+        );
+    }
+}
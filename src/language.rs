@@ -0,0 +1,127 @@
+use crate::{git, pattern};
+
+/// A programming language with the conventions used to tune prompts for it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Language {
+    pub name: &'static str,
+    extensions: &'static [&'static str],
+    /// Scope and convention guidance added to commit and PR prompts
+    pub hint: &'static str,
+}
+
+/// Languages git-ai knows conventions for
+const LANGUAGES: &[Language] = &[
+    Language {
+        name: "Rust",
+        extensions: &["rs"],
+        hint: "Use the crate or module name as the scope (e.g. `feat(parser): ...`, `fix(cli): ...`). \
+               Call out changes to public items, trait implementations, and Cargo features explicitly.",
+    },
+    Language {
+        name: "Python",
+        extensions: &["py", "pyi"],
+        hint: "Use the package or module path as the scope (e.g. `fix(api.auth): ...`). \
+               Mention changed public functions or classes and any dependency changes in pyproject/requirements.",
+    },
+    Language {
+        name: "TypeScript",
+        extensions: &["ts", "tsx"],
+        hint: "Use the package, component, or feature directory as the scope (e.g. `feat(button): ...`); \
+               in a monorepo prefer the workspace package name. Note changes to exported types.",
+    },
+    Language {
+        name: "JavaScript",
+        extensions: &["js", "jsx", "mjs", "cjs"],
+        hint: "Use the package, component, or feature directory as the scope (e.g. `feat(button): ...`); \
+               in a monorepo prefer the workspace package name.",
+    },
+    Language {
+        name: "Go",
+        extensions: &["go"],
+        hint: "Use the Go package name as the scope (e.g. `fix(http): ...`). \
+               Call out changes to exported identifiers and go.mod dependencies.",
+    },
+    Language {
+        name: "Java",
+        extensions: &["java"],
+        hint: "Use the module or top-level package name as the scope (e.g. `feat(billing): ...`). \
+               Mention changes to public classes and build files (pom.xml, build.gradle).",
+    },
+    Language {
+        name: "Kotlin",
+        extensions: &["kt", "kts"],
+        hint: "Use the Gradle module or top-level package name as the scope (e.g. `feat(billing): ...`).",
+    },
+    Language {
+        name: "C#",
+        extensions: &["cs"],
+        hint: "Use the project or namespace name as the scope (e.g. `fix(Api): ...`).",
+    },
+    Language {
+        name: "Ruby",
+        extensions: &["rb"],
+        hint: "Use the gem, model, or controller name as the scope (e.g. `feat(orders): ...`).",
+    },
+];
+
+/// Detect the language most of the tracked files are written in
+pub fn primary_language() -> Option<&'static Language> {
+    let files = git::run(&["ls-files"]).ok()?;
+    detect(files.lines().filter(|path| !pattern::should_exclude(path)))
+}
+
+/// Pick the language with the most files among `paths`
+fn detect<'a>(paths: impl Iterator<Item = &'a str>) -> Option<&'static Language> {
+    let mut counts = vec![0usize; LANGUAGES.len()];
+
+    for path in paths {
+        let Some((_, extension)) = path.rsplit_once('.') else {
+            continue;
+        };
+        if let Some(index) = LANGUAGES
+            .iter()
+            .position(|language| language.extensions.contains(&extension))
+        {
+            counts[index] += 1;
+        }
+    }
+
+    counts
+        .iter()
+        .enumerate()
+        .filter(|(_, &count)| count > 0)
+        // Earlier entries win ties, so TypeScript beats its compiled JavaScript
+        .max_by(|(a_index, a), (b_index, b)| a.cmp(b).then(b_index.cmp(a_index)))
+        .map(|(index, _)| &LANGUAGES[index])
+}
+
+/// Prompt section with conventions for the repository's primary language
+pub fn language_hint_section() -> Option<String> {
+    let language = primary_language()?;
+    Some(format!(
+        "Project language: {}. {}",
+        language.name, language.hint
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_primary_language() {
+        let files = [
+            "src/main.rs",
+            "src/lib.rs",
+            "build.py",
+            "README.md",
+            "Makefile",
+        ];
+        assert_eq!(detect(files.into_iter()).unwrap().name, "Rust");
+
+        let files = ["src/app.ts", "dist/app.js"];
+        assert_eq!(detect(files.into_iter()).unwrap().name, "TypeScript");
+
+        assert!(detect(["README.md", "LICENSE"].into_iter()).is_none());
+    }
+}
@@ -1,19 +1,43 @@
 mod cli;
 mod commands;
 mod config;
+mod context;
 mod cursor_agent;
-
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-use std::process::Command as StdCommand;
+mod git;
+mod gitignore;
+mod history;
+mod language;
+mod pattern;
+mod spellcheck;
+mod terminal;
+
+use anyhow::{Context, Result};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use cursor_agent::CursorAgent;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use terminal::{icon, Icon};
 
 #[derive(Parser)]
 #[command(name = "git-ai")]
 #[command(about = "AI-assisted git workflow with cursor-agent")]
-#[command(version = "0.7.1")]
+#[command(version)]
 struct Cli {
+    /// Run as if git-ai was started in <PATH> instead of the current directory
+    #[arg(short = 'C', global = true, value_name = "PATH")]
+    directory: Option<PathBuf>,
+
+    /// Use plain ASCII status prefixes instead of emoji
+    #[arg(long, global = true)]
+    no_emoji: bool,
+
+    /// Don't highlight previewed commit messages with color
+    #[arg(long, global = true)]
+    no_color: bool,
+
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
@@ -24,6 +48,50 @@ enum Commands {
         #[arg(short, long)]
         message: Option<String>,
 
+        /// Add a file's contents as extra context (repeatable)
+        #[arg(long = "include", value_name = "PATH")]
+        include: Vec<PathBuf>,
+
+        /// Give the AI the diffstat and let it read only the diffs it needs (for large changes)
+        #[arg(long)]
+        stat: bool,
+
+        /// Write a single subject line with no body
+        #[arg(long)]
+        oneline: bool,
+
+        /// Skip git commit hooks (passes --no-verify to git commit)
+        #[arg(long)]
+        no_verify: bool,
+
+        /// Don't add the ticket reference taken from the branch name
+        #[arg(long)]
+        no_ticket: bool,
+
+        /// Add a Signed-off-by trailer for your git identity (DCO)
+        #[arg(short = 's', long)]
+        signoff: bool,
+
+        /// Commit only the staged changes matching this pathspec, leaving other staged changes for later (repeatable)
+        #[arg(long = "only", value_name = "PATHSPEC")]
+        only: Vec<String>,
+
+        /// Format messages exactly like the message of this commit
+        #[arg(long, value_name = "REV")]
+        template_from_commit: Option<String>,
+
+        /// With --dry-run, ask the AI for its commit plan and print it as JSON instead of the prompt
+        #[arg(long, requires = "dry_run")]
+        json: bool,
+
+        /// Print only the generated commit message, without committing (e.g. for `git commit -F -`)
+        #[arg(long, conflicts_with = "json")]
+        message_only: bool,
+
+        /// Create an intentional empty commit, e.g. to trigger CI or mark a milestone
+        #[arg(long, conflicts_with = "only")]
+        allow_empty: bool,
+
         /// Skip user confirmation prompts
         #[arg(long)]
         no_confirm: bool,
@@ -42,6 +110,34 @@ enum Commands {
         #[arg(short, long)]
         message: Option<String>,
 
+        /// Add a file's contents as extra context (repeatable)
+        #[arg(long = "include", value_name = "PATH")]
+        include: Vec<PathBuf>,
+
+        /// Describe the changes in this patch file instead of the branch diff
+        #[arg(long, value_name = "FILE")]
+        patch: Option<PathBuf>,
+
+        /// Give the AI the branch diffstat and let it read only the diffs it needs (for large changes)
+        #[arg(long, conflicts_with = "patch")]
+        stat: bool,
+
+        /// Don't add the ticket reference taken from the branch name
+        #[arg(long)]
+        no_ticket: bool,
+
+        /// Print the description as a separate title and body
+        #[arg(long)]
+        split: bool,
+
+        /// Write the title and body to `title` and `body` files in this directory (implies --split)
+        #[arg(long, value_name = "DIR")]
+        output: Option<PathBuf>,
+
+        /// Suggest reviewers from the history of the changed files instead of writing a description
+        #[arg(long)]
+        reviewers: bool,
+
         /// Skip user confirmation prompts
         #[arg(long)]
         no_confirm: bool,
@@ -63,6 +159,75 @@ enum Commands {
         #[arg(short, long)]
         message: Option<String>,
 
+        /// Report conflicting files without merging or touching the working tree
+        #[arg(long)]
+        preview_conflicts: bool,
+
+        /// Merge even when the current branch is protected
+        #[arg(long)]
+        force: bool,
+
+        /// Skip user confirmation prompts
+        #[arg(long)]
+        no_confirm: bool,
+
+        /// Print the prompt without executing cursor-agent
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Show verbose output for debugging
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    /// Help resolve the conflicts of an in-progress merge, rebase, or cherry-pick
+    Resolve {
+        /// Custom message to guide the AI
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Skip user confirmation prompts
+        #[arg(long)]
+        no_confirm: bool,
+
+        /// Print the prompt without executing cursor-agent
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Show verbose output for debugging
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    /// Commit pending changes, push the branch, and generate the PR description
+    Ship {
+        /// Custom message to guide the AI
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Skip user confirmation prompts
+        #[arg(long)]
+        no_confirm: bool,
+
+        /// Print the prompts without executing cursor-agent or pushing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Show verbose output for debugging
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    /// Regenerate the message of an existing commit
+    Reword {
+        /// Commit to reword (e.g. HEAD~2 or a sha)
+        rev: String,
+
+        /// Custom message to guide the AI
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Reword even if the commit has already been pushed
+        #[arg(long)]
+        force: bool,
+
         /// Skip user confirmation prompts
         #[arg(long)]
         no_confirm: bool,
@@ -84,6 +249,26 @@ enum Commands {
         /// Generate sample configuration
         #[arg(long)]
         init: bool,
+
+        /// Upgrade existing config files to the current format (keeps a .bak backup)
+        #[arg(long)]
+        migrate: bool,
+
+        /// Print the fully-resolved configuration after merging all layers
+        #[arg(long)]
+        effective: bool,
+
+        /// Set a dotted key (e.g. commands.commit.tone) to a YAML value
+        #[arg(long, num_args = 2, value_names = ["KEY", "VALUE"], conflicts_with = "unset")]
+        set: Option<Vec<String>>,
+
+        /// Remove a dotted key so its default applies again
+        #[arg(long, value_name = "KEY")]
+        unset: Option<String>,
+
+        /// With --set/--unset, edit the user config instead of the repository's .git-ai.yaml
+        #[arg(long)]
+        global: bool,
     },
     /// Initialize a new project repository
     Init {
@@ -95,6 +280,26 @@ enum Commands {
         #[arg(short, long)]
         name: Option<String>,
 
+        /// Scaffold from flags and config without asking any questions
+        #[arg(long)]
+        non_interactive: bool,
+
+        /// Package manager or environment tool, e.g. uv, npm, cargo (with --non-interactive)
+        #[arg(long)]
+        package_manager: Option<String>,
+
+        /// Add a GitHub Actions CI workflow (with --non-interactive)
+        #[arg(long)]
+        ci: bool,
+
+        /// License to add, as an SPDX identifier such as MIT (with --non-interactive)
+        #[arg(long)]
+        license: Option<String>,
+
+        /// Don't create a README (with --non-interactive)
+        #[arg(long)]
+        no_readme: bool,
+
         /// Custom message to guide the AI
         #[arg(short, long)]
         message: Option<String>,
@@ -116,6 +321,52 @@ enum Commands {
         #[command(subcommand)]
         action: IgnoreAction,
     },
+    /// Revert the most recent commit(s) made by a git-ai command
+    Undo {
+        /// Skip user confirmation prompts
+        #[arg(long)]
+        no_confirm: bool,
+
+        /// Show what would be undone without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Check commits about to be pushed for risky changes (for use in a pre-push hook)
+    Prepush {
+        /// Report policy violations without failing
+        #[arg(long)]
+        no_block: bool,
+    },
+    /// Report how much of the repository's history was generated by git-ai
+    Stats,
+    /// Print shell completions, or install them with --install
+    Completions {
+        /// Shell to generate completions for (detected from $SHELL by default)
+        #[arg(long, value_enum)]
+        shell: Option<clap_complete::Shell>,
+
+        /// Write the completions where the shell loads them instead of printing
+        #[arg(long)]
+        install: bool,
+    },
+    /// Regenerate managed tooling files (.gitignore, .editorconfig) and commit them
+    AmendFiles {
+        /// Custom message to guide the AI
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Skip user confirmation prompts
+        #[arg(long)]
+        no_confirm: bool,
+
+        /// Print the prompt without executing cursor-agent
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Show verbose output for debugging
+        #[arg(short, long)]
+        verbose: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -158,12 +409,59 @@ enum IgnoreAction {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let args: Vec<OsString> = std::env::args_os().collect();
+    let (cli, parse_error) = match Cli::try_parse_from(&args) {
+        Ok(cli) => (cli, None),
+        // Without a subcommand, flags meant for `behavior.default_command` (such
+        // as `--verbose`) are unknown; keep the global ones until it is loaded
+        Err(e) if e.kind() == ErrorKind::UnknownArgument => match global_args(&args) {
+            Some(cli) if cli.command.is_none() => (cli, Some(e)),
+            _ => e.exit(),
+        },
+        Err(e) => e.exit(),
+    };
+
+    // Like `git -C`, every git and cursor-agent invocation inherits this directory
+    if let Some(ref directory) = cli.directory {
+        std::env::set_current_dir(directory)
+            .with_context(|| format!("Cannot change to directory: {}", directory.display()))?;
+    }
 
     // Load configuration (all commands get consistent access)
     let config = config::Config::load()?;
+    let cli = match (cli.command.is_some(), parse_error) {
+        (true, _) => cli,
+        (false, Some(e)) if config.behavior.default_command.is_none() => e.exit(),
+        (false, _) => default_command(config.behavior.default_command.as_deref(), &args[1..])?,
+    };
+    terminal::set_emoji(config.behavior.emoji && !cli.no_emoji);
+    terminal::set_preview(
+        config.behavior.color && !cli.no_color,
+        config.behavior.preview_width,
+    );
+    if let Some(ref binary) = config.git.binary {
+        git::set_binary(binary.clone());
+    }
+    if let Some(ref exclude) = config.context.exclude {
+        pattern::set_excludes(exclude.clone());
+    }
+    if let Some(max) = config.context.max_line_length {
+        context::set_max_line_length(max);
+    }
+    if let Some(ref always_include) = config.context.always_include {
+        context::set_always_include(always_include.clone());
+    }
+    let command = cli
+        .command
+        .context("No subcommand to run (set `behavior.default_command` in the config)")?;
+    if !matches!(
+        command,
+        Commands::Config { .. } | Commands::Completions { .. }
+    ) {
+        git::ensure_available()?;
+    }
 
-    let (_dry_run, verbose) = match &cli.command {
+    let (_dry_run, verbose) = match &command {
         Commands::Commit {
             dry_run, verbose, ..
         } => (*dry_run, *verbose),
@@ -173,10 +471,26 @@ async fn main() -> Result<()> {
         Commands::Merge {
             dry_run, verbose, ..
         } => (*dry_run, *verbose),
+        Commands::Resolve {
+            dry_run, verbose, ..
+        } => (*dry_run, *verbose),
+        Commands::Reword {
+            dry_run, verbose, ..
+        } => (*dry_run, *verbose),
+        Commands::Ship {
+            dry_run, verbose, ..
+        } => (*dry_run, *verbose),
         Commands::Init {
             dry_run, verbose, ..
         } => (*dry_run, *verbose),
+        Commands::AmendFiles {
+            dry_run, verbose, ..
+        } => (*dry_run, *verbose),
         Commands::Config { .. } => (false, false), // Config doesn't use cursor-agent
+        Commands::Undo { .. } => (false, false),   // Undo doesn't use cursor-agent
+        Commands::Prepush { .. } => (false, false), // Prepush doesn't use cursor-agent
+        Commands::Stats => (false, false),         // Stats doesn't use cursor-agent
+        Commands::Completions { .. } => (false, false), // Completions doesn't use cursor-agent
         Commands::Ignore { action } => match action {
             IgnoreAction::Add {
                 dry_run, verbose, ..
@@ -189,33 +503,105 @@ async fn main() -> Result<()> {
 
     // Override CLI flags with config values where appropriate
     let effective_verbose = verbose || config.behavior.verbose;
-    ensure_cursor_agent_available(effective_verbose)?;
+    let uses_agent = !matches!(
+        command,
+        Commands::Config { .. }
+            | Commands::Undo { .. }
+            | Commands::Prepush { .. }
+            | Commands::Stats
+            | Commands::Completions { .. }
+    );
+    let agent = CursorAgent::new(&config.agent);
+    if uses_agent {
+        ensure_cursor_agent_available(&agent, effective_verbose)?;
+    }
 
     // Dry run is now handled by individual commands
 
     if effective_verbose {
-        println!("🔧 Executing git-ai command...");
+        println!("{}Executing git-ai command...", icon(Icon::Working));
     }
 
-    let dispatcher = cli::CommandDispatcher::new(config);
-    dispatcher.dispatch(cli.command).await?;
+    let dispatcher = cli::CommandDispatcher::new(config, agent);
+    dispatcher.dispatch(command).await?;
 
     Ok(())
 }
 
+/// Global flags from a command line that names no subcommand, ignoring the
+/// arguments meant for the default command
+fn global_args(args: &[OsString]) -> Option<Cli> {
+    let matches = Cli::command()
+        .ignore_errors(true)
+        .try_get_matches_from(args)
+        .ok()?;
+    Cli::from_arg_matches(&matches).ok()
+}
+
+/// Parse the configured `behavior.default_command` into the command line to run
+/// when git-ai is invoked without a subcommand. The flags given on the command
+/// line (`args`, without the program name) follow the configured ones, so
+/// globals such as `-C` and command flags such as `--verbose` are kept.
+fn default_command(default: Option<&str>, args: &[OsString]) -> Result<Cli> {
+    let Some(default) = default.map(str::trim).filter(|default| !default.is_empty()) else {
+        Cli::command()
+            .error(
+                clap::error::ErrorKind::MissingSubcommand,
+                "a subcommand is required (or set `behavior.default_command` in the config)",
+            )
+            .exit();
+    };
+
+    let configured =
+        Cli::try_parse_from(std::iter::once("git-ai").chain(default.split_whitespace()))
+            .with_context(|| format!("Invalid `behavior.default_command`: {}", default))?;
+    if configured.command.is_none() {
+        anyhow::bail!(
+            "`behavior.default_command` names no subcommand: {}",
+            default
+        );
+    }
+    if configured.directory.is_some() {
+        anyhow::bail!(
+            "`behavior.default_command` cannot change the directory with -C: {}",
+            default
+        );
+    }
+
+    let argv = std::iter::once(OsString::from("git-ai"))
+        .chain(default.split_whitespace().map(OsString::from))
+        .chain(args.iter().cloned());
+    Ok(Cli::try_parse_from(argv).unwrap_or_else(|e| e.exit()))
+}
+
 /// Ensure cursor-agent is available on the system
-fn ensure_cursor_agent_available(verbose: bool) -> Result<()> {
-    if let Ok(output) = StdCommand::new("cursor-agent").arg("--version").output() {
-        if output.status.success() {
-            if verbose {
-                let version = String::from_utf8_lossy(&output.stdout);
-                println!("✅ cursor-agent found: {}", version.trim());
-            }
-            return Ok(());
+fn ensure_cursor_agent_available(agent: &CursorAgent, verbose: bool) -> Result<()> {
+    if let Some(version) = agent.version() {
+        if verbose {
+            println!("{}cursor-agent found: {}", icon(Icon::Ok), version);
         }
+        return Ok(());
     }
 
-    eprintln!("❌ cursor-agent is not installed or not found in PATH");
+    if agent.binary() != Path::new("cursor-agent") {
+        eprintln!(
+            "{}cursor-agent could not be run from {}",
+            icon(Icon::Error),
+            agent.binary().display()
+        );
+        eprintln!();
+        eprintln!(
+            "Check the `agent.binary` config setting and the {} environment variable.",
+            cursor_agent::AGENT_BIN_ENV
+        );
+
+        anyhow::bail!("cursor-agent not found");
+    }
+
+    eprintln!(
+        "{}cursor-agent is not installed or not found in PATH",
+        icon(Icon::Error)
+    );
     eprintln!();
     eprintln!("Please install cursor-agent before using git-ai:");
     eprintln!("  Visit: https://cursor.com/");
@@ -232,21 +618,42 @@ fn ensure_cursor_agent_available(verbose: bool) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use clap::{CommandFactory, Parser};
 
     #[test]
     fn test_cli_parsing_commit_command() {
         let args = vec!["git-ai", "commit", "-m", "test message", "--no-confirm"];
         let cli = Cli::try_parse_from(args).unwrap();
 
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Commit {
                 message,
+                include,
+                stat,
+                oneline,
+                no_verify,
+                no_ticket,
+                signoff,
+                only,
+                template_from_commit,
+                json,
+                message_only,
+                allow_empty,
                 no_confirm,
                 dry_run,
                 verbose,
             } => {
                 assert_eq!(message, Some("test message".to_string()));
+                assert!(include.is_empty());
+                assert!(!stat);
+                assert!(!oneline);
+                assert!(!no_verify);
+                assert!(!no_ticket);
+                assert!(!signoff);
+                assert!(only.is_empty());
+                assert_eq!(template_from_commit, None);
+                assert!(!json);
+                assert!(!message_only);
+                assert!(!allow_empty);
                 assert!(no_confirm);
                 assert!(!dry_run);
                 assert!(!verbose);
@@ -260,14 +667,36 @@ mod tests {
         let args = vec!["git-ai", "commit"];
         let cli = Cli::try_parse_from(args).unwrap();
 
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Commit {
                 message,
+                include,
+                stat,
+                oneline,
+                no_verify,
+                no_ticket,
+                signoff,
+                only,
+                template_from_commit,
+                json,
+                message_only,
+                allow_empty,
                 no_confirm,
                 dry_run,
                 verbose,
             } => {
                 assert_eq!(message, None);
+                assert!(include.is_empty());
+                assert!(!stat);
+                assert!(!oneline);
+                assert!(!no_verify);
+                assert!(!no_ticket);
+                assert!(!signoff);
+                assert!(only.is_empty());
+                assert_eq!(template_from_commit, None);
+                assert!(!json);
+                assert!(!message_only);
+                assert!(!allow_empty);
                 assert!(!no_confirm);
                 assert!(!dry_run);
                 assert!(!verbose);
@@ -276,19 +705,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_commit_json_requires_dry_run() {
+        assert!(Cli::try_parse_from(["git-ai", "commit", "--json"]).is_err());
+
+        let cli = Cli::try_parse_from(["git-ai", "commit", "--dry-run", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Commit {
+                json: true,
+                dry_run: true,
+                ..
+            })
+        ));
+
+        assert!(
+            Cli::try_parse_from(["git-ai", "commit", "--dry-run", "--json", "--message-only"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_default_command() {
+        let cli = Cli::try_parse_from(["git-ai"]).unwrap();
+        assert!(cli.command.is_none());
+
+        let cli = default_command(Some("commit --no-verify"), &[]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Commit {
+                no_verify: true,
+                ..
+            })
+        ));
+
+        let args: Vec<OsString> = ["-C", "repo", "--no-emoji", "--verbose"]
+            .into_iter()
+            .map(OsString::from)
+            .collect();
+        let cli = default_command(Some("commit --no-verify"), &args).unwrap();
+        assert_eq!(cli.directory.as_deref(), Some(Path::new("repo")));
+        assert!(cli.no_emoji);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Commit {
+                no_verify: true,
+                verbose: true,
+                ..
+            })
+        ));
+
+        assert!(default_command(Some("commit -C elsewhere"), &[]).is_err());
+
+        assert!(default_command(Some("frobnicate"), &[]).is_err());
+    }
+
     #[test]
     fn test_cli_parsing_pr_command() {
         let args = vec!["git-ai", "pr", "--message", "pr description"];
         let cli = Cli::try_parse_from(args).unwrap();
 
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Pr {
                 message,
+                include,
+                patch,
+                stat,
+                no_ticket,
+                split,
+                output,
+                reviewers,
                 no_confirm,
                 dry_run,
                 verbose,
             } => {
                 assert_eq!(message, Some("pr description".to_string()));
+                assert!(include.is_empty());
+                assert_eq!(patch, None);
+                assert!(!stat);
+                assert!(!no_ticket);
+                assert!(!split);
+                assert_eq!(output, None);
+                assert!(!reviewers);
                 assert!(!no_confirm);
                 assert!(!dry_run);
                 assert!(!verbose);
@@ -297,6 +795,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_repeated_include() {
+        let args = vec![
+            "git-ai",
+            "commit",
+            "--include",
+            "docs/DESIGN.md",
+            "--include",
+            "CONTRIBUTING.md",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        match cli.command.unwrap() {
+            Commands::Commit { include, .. } => {
+                assert_eq!(
+                    include,
+                    vec![
+                        PathBuf::from("docs/DESIGN.md"),
+                        PathBuf::from("CONTRIBUTING.md")
+                    ]
+                );
+            }
+            _ => panic!("Expected commit command"),
+        }
+    }
+
     #[test]
     fn test_cli_parsing_merge_command() {
         let args = vec![
@@ -309,16 +833,20 @@ mod tests {
         ];
         let cli = Cli::try_parse_from(args).unwrap();
 
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Merge {
                 branch,
                 message,
+                preview_conflicts,
+                force,
                 no_confirm,
                 dry_run,
                 verbose,
             } => {
                 assert_eq!(branch, "feature/branch");
                 assert_eq!(message, Some("merge message".to_string()));
+                assert!(!preview_conflicts);
+                assert!(!force);
                 assert!(no_confirm);
                 assert!(!dry_run);
                 assert!(!verbose);
@@ -332,16 +860,20 @@ mod tests {
         let args = vec!["git-ai", "merge", "main"];
         let cli = Cli::try_parse_from(args).unwrap();
 
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Merge {
                 branch,
                 message,
+                preview_conflicts,
+                force,
                 no_confirm,
                 dry_run,
                 verbose,
             } => {
                 assert_eq!(branch, "main");
                 assert_eq!(message, None);
+                assert!(!preview_conflicts);
+                assert!(!force);
                 assert!(!no_confirm);
                 assert!(!dry_run);
                 assert!(!verbose);
@@ -350,10 +882,111 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_amend_files_command() {
+        let args = vec!["git-ai", "amend-files", "--dry-run"];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        match cli.command.unwrap() {
+            Commands::AmendFiles {
+                message,
+                no_confirm,
+                dry_run,
+                verbose,
+            } => {
+                assert_eq!(message, None);
+                assert!(!no_confirm);
+                assert!(dry_run);
+                assert!(!verbose);
+            }
+            _ => panic!("Expected amend-files command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_directory_override() {
+        let args = vec!["git-ai", "-C", "../other-repo", "commit"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert_eq!(cli.directory, Some(PathBuf::from("../other-repo")));
+
+        // Global, so it is also accepted after the subcommand
+        let args = vec!["git-ai", "pr", "-C", "/tmp/repo"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert_eq!(cli.directory, Some(PathBuf::from("/tmp/repo")));
+    }
+
+    #[test]
+    fn test_cli_parsing_config_set() {
+        let args = vec!["git-ai", "config", "--set", "commands.commit.tone", "terse"];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        match cli.command.unwrap() {
+            Commands::Config {
+                set, unset, global, ..
+            } => {
+                assert_eq!(
+                    set,
+                    Some(vec![
+                        "commands.commit.tone".to_string(),
+                        "terse".to_string()
+                    ])
+                );
+                assert_eq!(unset, None);
+                assert!(!global);
+            }
+            _ => panic!("Expected config command"),
+        }
+
+        assert!(Cli::try_parse_from(["git-ai", "config", "--set", "behavior.verbose"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_ship_command() {
+        let args = vec!["git-ai", "ship", "-m", "Small fix", "--no-confirm"];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        match cli.command.unwrap() {
+            Commands::Ship {
+                message,
+                no_confirm,
+                dry_run,
+                verbose,
+            } => {
+                assert_eq!(message, Some("Small fix".to_string()));
+                assert!(no_confirm);
+                assert!(!dry_run);
+                assert!(!verbose);
+            }
+            _ => panic!("Expected ship command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_completions() {
+        let args = vec!["git-ai", "completions", "--shell", "zsh", "--install"];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        match cli.command.unwrap() {
+            Commands::Completions { shell, install } => {
+                assert_eq!(shell, Some(clap_complete::Shell::Zsh));
+                assert!(install);
+            }
+            _ => panic!("Expected completions command"),
+        }
+
+        assert!(Cli::try_parse_from(["git-ai", "completions", "--shell", "tcsh"]).is_err());
+    }
+
     #[test]
     fn test_cli_name() {
         let cli = Cli::command();
         let name = cli.get_name();
         assert_eq!(name, "git-ai");
     }
+
+    #[test]
+    fn test_cli_version_matches_package() {
+        let cli = Cli::command();
+        assert_eq!(cli.get_version(), Some(env!("CARGO_PKG_VERSION")));
+    }
 }
@@ -0,0 +1,131 @@
+use std::sync::OnceLock;
+
+/// Generated and vendored directories left out of context when none are configured
+pub const DEFAULT_EXCLUDES: &[&str] = &["vendor/", "node_modules/", "target/", "dist/"];
+
+/// Patterns from the `context.exclude` setting, set once at startup
+static EXCLUDES: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Use `patterns` instead of [`DEFAULT_EXCLUDES`] for every context scan
+pub fn set_excludes(patterns: Vec<String>) {
+    let _ = EXCLUDES.set(patterns);
+}
+
+/// Whether a path is generated or vendored and should be left out of context
+pub fn should_exclude(path: &str) -> bool {
+    match EXCLUDES.get() {
+        Some(patterns) => matches_any(patterns, path),
+        None => matches_any(DEFAULT_EXCLUDES, path),
+    }
+}
+
+/// Check whether a path matches a simple glob pattern.
+///
+/// Patterns follow a small subset of `.gitignore` semantics:
+/// - `dir/` matches any path containing a `dir` directory component
+/// - patterns without a `/` are matched against the file name only
+/// - other patterns are matched against the full path
+///
+/// `*` matches any run of characters and `?` matches a single character.
+pub fn matches(pattern: &str, path: &str) -> bool {
+    if let Some(dir) = pattern.strip_suffix('/') {
+        let mut components: Vec<&str> = path.split('/').collect();
+        // The last component is the file itself, not a directory
+        components.pop();
+        return components
+            .iter()
+            .any(|component| wildcard_match(dir, component));
+    }
+
+    if !pattern.contains('/') {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        return wildcard_match(pattern, file_name);
+    }
+
+    wildcard_match(pattern, path)
+}
+
+/// Check whether a path matches any of the given patterns
+pub fn matches_any<S: AsRef<str>>(patterns: &[S], path: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| matches(pattern.as_ref(), path))
+}
+
+/// Match `text` against a pattern containing `*` and `?` wildcards
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            // Let the last `*` absorb one more character and retry
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*_test.rs", "config_test.rs"));
+        assert!(wildcard_match("*.spec.ts", "app.spec.ts"));
+        assert!(wildcard_match("release/*", "release/1.0"));
+        assert!(wildcard_match("v?.0", "v1.0"));
+        assert!(!wildcard_match("*_test.rs", "config.rs"));
+        assert!(!wildcard_match("main", "maintenance"));
+    }
+
+    #[test]
+    fn test_default_excludes() {
+        assert!(matches_any(
+            DEFAULT_EXCLUDES,
+            "vendor/github.com/pkg/errors/errors.go"
+        ));
+        assert!(matches_any(
+            DEFAULT_EXCLUDES,
+            "web/node_modules/react/index.js"
+        ));
+        assert!(matches_any(DEFAULT_EXCLUDES, "target/debug/build.rs"));
+        assert!(!matches_any(DEFAULT_EXCLUDES, "src/vendor.rs"));
+        assert!(!matches_any(DEFAULT_EXCLUDES, "docs/distribution.md"));
+    }
+
+    #[test]
+    fn test_directory_patterns() {
+        assert!(matches("tests/", "tests/cli.rs"));
+        assert!(matches("tests/", "crates/core/tests/cli.rs"));
+        assert!(!matches("tests/", "src/tests.rs"));
+    }
+
+    #[test]
+    fn test_file_name_patterns() {
+        assert!(matches("*_test.go", "pkg/server/handler_test.go"));
+        assert!(!matches("*_test.go", "pkg/server/handler.go"));
+    }
+
+    #[test]
+    fn test_full_path_patterns() {
+        assert!(matches("src/*.rs", "src/main.rs"));
+        assert!(!matches("src/*.rs", "lib/main.rs"));
+        assert!(matches_any(&["docs/", "*.md"], "README.md"));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::terminal::{icon, Icon};
+
+/// Common English misspellings and their corrections. Only listed words are
+/// flagged, so identifiers and jargon never produce false positives.
+const MISSPELLINGS: &[(&str, &str)] = &[
+    ("accomodate", "accommodate"),
+    ("accross", "across"),
+    ("acheive", "achieve"),
+    ("adress", "address"),
+    ("alot", "a lot"),
+    ("arguement", "argument"),
+    ("asyncronous", "asynchronous"),
+    ("authenication", "authentication"),
+    ("availabe", "available"),
+    ("availible", "available"),
+    ("begining", "beginning"),
+    ("beleive", "believe"),
+    ("cacheing", "caching"),
+    ("chnage", "change"),
+    ("comming", "coming"),
+    ("commited", "committed"),
+    ("commiting", "committing"),
+    ("comparision", "comparison"),
+    ("compatability", "compatibility"),
+    ("completly", "completely"),
+    ("configuation", "configuration"),
+    ("consistant", "consistent"),
+    ("definately", "definitely"),
+    ("defualt", "default"),
+    ("dependancies", "dependencies"),
+    ("dependancy", "dependency"),
+    ("depricated", "deprecated"),
+    ("enviornment", "environment"),
+    ("enviroment", "environment"),
+    ("exeption", "exception"),
+    ("existant", "existent"),
+    ("explicitely", "explicitly"),
+    ("finaly", "finally"),
+    ("foward", "forward"),
+    ("fucntion", "function"),
+    ("funtion", "function"),
+    ("garantee", "guarantee"),
+    ("handeling", "handling"),
+    ("happend", "happened"),
+    ("hte", "the"),
+    ("immediatly", "immediately"),
+    ("implemention", "implementation"),
+    ("improvment", "improvement"),
+    ("independant", "independent"),
+    ("instad", "instead"),
+    ("intial", "initial"),
+    ("intialize", "initialize"),
+    ("langauge", "language"),
+    ("lenght", "length"),
+    ("maintainance", "maintenance"),
+    ("managment", "management"),
+    ("mesage", "message"),
+    ("messsage", "message"),
+    ("neccessary", "necessary"),
+    ("necesary", "necessary"),
+    ("occurance", "occurrence"),
+    ("occured", "occurred"),
+    ("occurence", "occurrence"),
+    ("optinal", "optional"),
+    ("overriden", "overridden"),
+    ("paramter", "parameter"),
+    ("performace", "performance"),
+    ("persistant", "persistent"),
+    ("posible", "possible"),
+    ("prefered", "preferred"),
+    ("previosly", "previously"),
+    ("proccess", "process"),
+    ("publically", "publicly"),
+    ("recieve", "receive"),
+    ("recieved", "received"),
+    ("recomend", "recommend"),
+    ("refered", "referred"),
+    ("refrence", "reference"),
+    ("relevent", "relevant"),
+    ("remvoe", "remove"),
+    ("reponse", "response"),
+    ("repositry", "repository"),
+    ("requirment", "requirement"),
+    ("retreive", "retrieve"),
+    ("sepcific", "specific"),
+    ("seperate", "separate"),
+    ("seperately", "separately"),
+    ("succesful", "successful"),
+    ("successfull", "successful"),
+    ("sucess", "success"),
+    ("suport", "support"),
+    ("supress", "suppress"),
+    ("sytem", "system"),
+    ("taht", "that"),
+    ("teh", "the"),
+    ("tempory", "temporary"),
+    ("thier", "their"),
+    ("threshhold", "threshold"),
+    ("truely", "truly"),
+    ("udpate", "update"),
+    ("unecessary", "unnecessary"),
+    ("unneccessary", "unnecessary"),
+    ("untill", "until"),
+    ("usefull", "useful"),
+    ("varaible", "variable"),
+    ("verison", "version"),
+    ("wether", "whether"),
+    ("wich", "which"),
+    ("wierd", "weird"),
+    ("writen", "written"),
+];
+
+/// Whether `line` is a `Key: value` trailer such as `Co-authored-by:`
+fn is_trailer(line: &str) -> bool {
+    line.split_once(": ").is_some_and(|(key, _)| {
+        !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+/// Misspelled words in a commit message, each with its likely correction, in
+/// order of first appearance. Code in backticks and trailers are skipped.
+pub fn typos(message: &str) -> Vec<(String, &'static str)> {
+    let mut found: Vec<(String, &'static str)> = Vec::new();
+
+    for (index, line) in message.lines().enumerate() {
+        // The subject's `type: ...` looks like a trailer, but never is one
+        if index > 0 && is_trailer(line) {
+            continue;
+        }
+
+        // Every other backtick-delimited span is code
+        for prose in line.split('`').step_by(2) {
+            for word in prose.split(|c: char| !c.is_alphabetic()) {
+                let lower = word.to_lowercase();
+                let Some((_, correction)) = MISSPELLINGS.iter().find(|(typo, _)| *typo == lower)
+                else {
+                    continue;
+                };
+                if !found.iter().any(|(seen, _)| seen.to_lowercase() == lower) {
+                    found.push((word.to_string(), correction));
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// Warn about likely typos in a generated message. Goes to stderr so
+/// `--message-only` output can still be piped into `git commit -F -`.
+pub fn warn(message: &str) {
+    let found = typos(message);
+    if found.is_empty() {
+        return;
+    }
+
+    let listed = found
+        .iter()
+        .map(|(word, correction)| format!("{} ({}?)", word, correction))
+        .collect::<Vec<_>>()
+        .join(", ");
+    eprintln!(
+        "{}Possible typos in the message: {}",
+        icon(Icon::Warn),
+        listed
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typos() {
+        let message = "fix: seperate teh parser from `recieve_loop`\n\n\
+                       - Teh lexer no longer does this\n\
+                       - Handle the occured case\n\n\
+                       Co-authored-by: Teh Author <teh@example.com>";
+        assert_eq!(
+            typos(message),
+            vec![
+                ("seperate".to_string(), "separate"),
+                ("teh".to_string(), "the"),
+                ("occured".to_string(), "occurred"),
+            ]
+        );
+        assert!(typos("feat: add the receive loop").is_empty());
+    }
+
+    #[test]
+    fn test_misspellings_are_sorted_lowercase() {
+        assert!(MISSPELLINGS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(MISSPELLINGS
+            .iter()
+            .all(|(typo, _)| *typo == typo.to_lowercase()));
+    }
+}
@@ -0,0 +1,339 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Whether status prefixes use emoji (the default) or plain ASCII
+static EMOJI: AtomicBool = AtomicBool::new(true);
+
+/// Whether previewed commit messages are highlighted with ANSI colors
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Column previewed commit messages are wrapped at; 0 leaves them unwrapped
+static PREVIEW_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Preview width when neither the config nor `COLUMNS` sets one
+const DEFAULT_PREVIEW_WIDTH: usize = 80;
+
+/// Indentation of previewed messages under their heading
+const MESSAGE_INDENT: &str = "   ";
+
+const BOLD_YELLOW: &str = "\x1b[1;33m";
+const CYAN: &str = "\x1b[36m";
+const BOLD_RED: &str = "\x1b[1;31m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// Kinds of status prefixes used in console output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    Ok,
+    Error,
+    Warn,
+    Info,
+    Hint,
+    Search,
+    Working,
+    Files,
+    Undo,
+    Saved,
+}
+
+impl Icon {
+    fn emoji(self) -> &'static str {
+        match self {
+            Icon::Ok => "✅ ",
+            Icon::Error => "❌ ",
+            Icon::Warn => "⚠️  ",
+            Icon::Info => "ℹ️  ",
+            Icon::Hint => "💡 ",
+            Icon::Search => "🔍 ",
+            Icon::Working => "🔧 ",
+            Icon::Files => "📄 ",
+            Icon::Undo => "↩️  ",
+            Icon::Saved => "💾 ",
+        }
+    }
+
+    fn plain(self) -> &'static str {
+        match self {
+            Icon::Ok => "[ok] ",
+            Icon::Error => "[error] ",
+            Icon::Warn => "[warn] ",
+            Icon::Info => "[info] ",
+            Icon::Hint => "[hint] ",
+            Icon::Search => "[*] ",
+            Icon::Working => "[*] ",
+            Icon::Files => "[files] ",
+            Icon::Undo => "[undo] ",
+            Icon::Saved => "[saved] ",
+        }
+    }
+}
+
+/// Choose between emoji and plain ASCII status prefixes for this process
+pub fn set_emoji(enabled: bool) {
+    EMOJI.store(enabled, Ordering::Relaxed);
+}
+
+/// Status prefix for console output, including its trailing spacing
+pub fn icon(icon: Icon) -> &'static str {
+    if EMOJI.load(Ordering::Relaxed) {
+        icon.emoji()
+    } else {
+        icon.plain()
+    }
+}
+
+/// Configure how [`render_message`] previews messages. Color and wrapping are
+/// only used when stdout is a terminal; `NO_COLOR` also disables color.
+pub fn set_preview(color: bool, width: Option<usize>) {
+    let tty = io::stdout().is_terminal();
+    COLOR.store(
+        color && tty && std::env::var_os("NO_COLOR").is_none(),
+        Ordering::Relaxed,
+    );
+
+    let width = if tty {
+        width
+            .or_else(|| std::env::var("COLUMNS").ok()?.trim().parse().ok())
+            .unwrap_or(DEFAULT_PREVIEW_WIDTH)
+    } else {
+        0
+    };
+    PREVIEW_WIDTH.store(width, Ordering::Relaxed);
+}
+
+/// Indent a commit message for display under a heading, wrapped to the
+/// preview width and with its conventional-commit type highlighted
+pub fn render_message(message: &str) -> String {
+    format_message(
+        message,
+        PREVIEW_WIDTH.load(Ordering::Relaxed),
+        COLOR.load(Ordering::Relaxed),
+    )
+}
+
+fn format_message(message: &str, width: usize, color: bool) -> String {
+    let width = width.saturating_sub(MESSAGE_INDENT.len());
+    let mut lines = Vec::new();
+
+    for (index, line) in message.trim_end().lines().enumerate() {
+        if line.is_empty() {
+            lines.push(String::new());
+        } else if index == 0 {
+            let subject = if color {
+                highlight_subject(line)
+            } else {
+                line.to_string()
+            };
+            lines.push(format!("{}{}", MESSAGE_INDENT, subject));
+        } else {
+            lines.extend(
+                wrap_line(line, width)
+                    .into_iter()
+                    .map(|line| format!("{}{}", MESSAGE_INDENT, line)),
+            );
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Line diff from `old` to `new` message, indented for display, with removed
+/// lines prefixed `-` and added lines `+` (colored when color is enabled)
+pub fn render_message_diff(old: &str, new: &str) -> String {
+    format_message_diff(old, new, COLOR.load(Ordering::Relaxed))
+}
+
+fn format_message_diff(old: &str, new: &str, color: bool) -> String {
+    line_diff(old.trim_end(), new.trim_end())
+        .into_iter()
+        .map(|(change, line)| {
+            let (start, end) = match (color, change) {
+                (true, '-') => (RED, RESET),
+                (true, '+') => (GREEN, RESET),
+                _ => ("", ""),
+            };
+            format!("{}{}{} {}{}", MESSAGE_INDENT, start, change, line, end)
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Minimal line diff via longest common subsequence. Each line is tagged
+/// `' '` (unchanged), `'-'` (only in `old`), or `'+'` (only in `new`).
+fn line_diff<'a>(old: &'a str, new: &'a str) -> Vec<(char, &'a str)> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // common[i][j]: length of the LCS of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::new();
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            diff.push(('-', old[i]));
+            i += 1;
+        } else {
+            diff.push(('+', new[j]));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|line| ('-', *line)));
+    diff.extend(new[j..].iter().map(|line| ('+', *line)));
+    diff
+}
+
+/// Color the type, scope, and breaking-change marker of a conventional subject
+fn highlight_subject(subject: &str) -> String {
+    let pattern = Regex::new(r"^(\w+)(\([^)]*\))?(!)?:").expect("valid subject pattern");
+    let Some(captures) = pattern.captures(subject) else {
+        return subject.to_string();
+    };
+
+    let mut highlighted = format!("{}{}{}", BOLD_YELLOW, &captures[1], RESET);
+    if let Some(scope) = captures.get(2) {
+        highlighted.push_str(&format!("{}{}{}", CYAN, scope.as_str(), RESET));
+    }
+    if captures.get(3).is_some() {
+        highlighted.push_str(&format!("{}!{}", BOLD_RED, RESET));
+    }
+    highlighted.push_str(&subject[captures[0].len() - 1..]);
+    highlighted
+}
+
+/// Greedily wrap a body line at `width` columns. Continuation lines of a
+/// bullet are indented to its text. Words longer than the width are kept whole.
+fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    if width == 0 || line.chars().count() <= width {
+        return vec![line.to_string()];
+    }
+
+    let text = line.trim_start();
+    let leading = &line[..line.len() - text.len()];
+    let hanging = if text.starts_with("- ") || text.starts_with("* ") {
+        format!("{}  ", leading)
+    } else {
+        leading.to_string()
+    };
+
+    let mut lines = Vec::new();
+    let mut current = leading.to_string();
+    for word in text.split_whitespace() {
+        let prefix_only = current.trim().is_empty();
+        if !prefix_only && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::replace(&mut current, hanging.clone()));
+        }
+        if !current.trim().is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    lines.push(current);
+    lines
+}
+
+/// Ask the user a yes/no question, defaulting to "no". Fails instead of
+/// reading EOF as "no" when stdin is not interactive (piped input, CI).
+pub fn confirm(question: &str) -> Result<bool> {
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("confirmation required but stdin is not interactive; pass --no-confirm");
+    }
+
+    print!("{} [y/N] ", question);
+    io::stdout().flush().context("Failed to flush stdout")?;
+
+    let mut response = String::new();
+    io::stdin()
+        .read_line(&mut response)
+        .context("Failed to read confirmation from stdin")?;
+
+    Ok(matches!(
+        response.trim().to_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_icons_are_ascii() {
+        let all = [
+            Icon::Ok,
+            Icon::Error,
+            Icon::Warn,
+            Icon::Info,
+            Icon::Hint,
+            Icon::Search,
+            Icon::Working,
+            Icon::Files,
+            Icon::Undo,
+            Icon::Saved,
+        ];
+        for icon in all {
+            assert!(icon.plain().is_ascii());
+            assert!(icon.plain().ends_with(' '));
+        }
+    }
+
+    #[test]
+    fn test_format_message_wraps_body() {
+        let message = "feat(api): add login\n\n- validate the token and reject requests whose claims expired\n";
+        assert_eq!(
+            format_message(message, 40, false),
+            "   feat(api): add login\n\n   - validate the token and reject\n     requests whose claims expired"
+        );
+        assert_eq!(
+            format_message(message, 0, false),
+            "   feat(api): add login\n\n   - validate the token and reject requests whose claims expired"
+        );
+    }
+
+    #[test]
+    fn test_line_diff() {
+        let old = "fix: typo\n\n- fix readme";
+        let new = "docs: fix typo in readme\n\n- fix readme";
+        assert_eq!(
+            line_diff(old, new),
+            vec![
+                ('-', "fix: typo"),
+                ('+', "docs: fix typo in readme"),
+                (' ', ""),
+                (' ', "- fix readme"),
+            ]
+        );
+        assert_eq!(
+            format_message_diff(old, new, false),
+            "   - fix: typo\n   + docs: fix typo in readme\n\n     - fix readme"
+        );
+    }
+
+    #[test]
+    fn test_highlight_subject() {
+        assert_eq!(
+            highlight_subject("feat(api)!: drop v1"),
+            "\x1b[1;33mfeat\x1b[0m\x1b[36m(api)\x1b[0m\x1b[1;31m!\x1b[0m: drop v1"
+        );
+        assert_eq!(highlight_subject("Update readme"), "Update readme");
+    }
+}
@@ -0,0 +1,62 @@
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Run `program` in `dir` with an isolated home, git identity, and a fake agent
+fn run(program: &str, args: &[&str], dir: &Path, home: &Path) -> Output {
+    Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .env("GIT_AUTHOR_NAME", "Ada Lovelace")
+        .env("GIT_AUTHOR_EMAIL", "ada@example.com")
+        .env("GIT_COMMITTER_NAME", "Ada Lovelace")
+        .env("GIT_COMMITTER_EMAIL", "ada@example.com")
+        .env("GITAI_AGENT_BIN", home.join("fake-agent"))
+        .output()
+        .unwrap()
+}
+
+fn git(args: &[&str], dir: &Path, home: &Path) -> String {
+    let output = run("git", args, dir, home);
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn test_reword_from_subdirectory() {
+    let home = tempfile::tempdir().unwrap();
+    let repo = tempfile::tempdir().unwrap();
+    let (home, repo) = (home.path(), repo.path());
+
+    let agent = home.join("fake-agent");
+    fs::write(&agent, "#!/bin/sh\necho 'fix(lib): handle empty input'\n").unwrap();
+    fs::set_permissions(&agent, fs::Permissions::from_mode(0o755)).unwrap();
+
+    git(&["init", "-q"], repo, home);
+    fs::create_dir(repo.join("src")).unwrap();
+    fs::write(repo.join("src/lib.rs"), "fn main() {}\n").unwrap();
+    git(&["add", "."], repo, home);
+    git(&["commit", "-q", "-m", "wip"], repo, home);
+
+    let output = run(
+        env!("CARGO_BIN_EXE_git-ai"),
+        &["reword", "HEAD", "--no-confirm"],
+        &repo.join("src"),
+        home,
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        git(&["log", "-1", "--format=%s"], repo, home),
+        "fix(lib): handle empty input"
+    );
+}